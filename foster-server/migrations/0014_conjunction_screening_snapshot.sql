@@ -0,0 +1,6 @@
+-- Chunks index into one fixed TLE array, so every replica that claims a
+-- chunk has to screen the exact array (and window) the coordinator split —
+-- snapshotted on the parent row rather than re-fetched per replica, since
+-- CelesTrak can publish new element sets mid-screening.
+ALTER TABLE conjunction_screenings ADD COLUMN IF NOT EXISTS tle_snapshot JSONB;
+ALTER TABLE conjunction_screenings ADD COLUMN IF NOT EXISTS window_start_unix_ms DOUBLE PRECISION;
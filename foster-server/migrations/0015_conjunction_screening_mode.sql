@@ -0,0 +1,4 @@
+-- Which close-approach search the screening's chunks run ('coarse' sample
+-- threshold or 'fine' range-rate bracketing). Every screening before this
+-- column existed was a coarse one.
+ALTER TABLE conjunction_screenings ADD COLUMN IF NOT EXISTS mode TEXT NOT NULL DEFAULT 'coarse';
@@ -0,0 +1,6 @@
+-- 2D (Foster/Alfano) collision probability at TCA. Nullable: events from
+-- screenings before this column existed never had one computed.
+ALTER TABLE conjunction_events ADD COLUMN IF NOT EXISTS collision_probability DOUBLE PRECISION;
+
+CREATE INDEX IF NOT EXISTS idx_conjunction_events_pc
+    ON conjunction_events (screening_id, collision_probability DESC NULLS LAST);
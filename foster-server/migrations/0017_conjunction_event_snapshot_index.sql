@@ -0,0 +1,5 @@
+-- Each event's two objects as positions in its screening's tle_snapshot, so
+-- the exact element sets screened can be re-propagated (CDM export).
+-- Nullable: events stored before this column existed can't be traced back.
+ALTER TABLE conjunction_events ADD COLUMN IF NOT EXISTS sat_a_index INTEGER;
+ALTER TABLE conjunction_events ADD COLUMN IF NOT EXISTS sat_b_index INTEGER;
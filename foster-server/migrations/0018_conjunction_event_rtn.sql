@@ -0,0 +1,7 @@
+-- Miss vector at TCA in the first object's radial/in-track/cross-track
+-- frame, plus the angle between the two velocity vectors. Nullable: older
+-- events only ever stored the scalar miss distance.
+ALTER TABLE conjunction_events ADD COLUMN IF NOT EXISTS miss_radial_km REAL;
+ALTER TABLE conjunction_events ADD COLUMN IF NOT EXISTS miss_in_track_km REAL;
+ALTER TABLE conjunction_events ADD COLUMN IF NOT EXISTS miss_cross_track_km REAL;
+ALTER TABLE conjunction_events ADD COLUMN IF NOT EXISTS approach_angle_deg REAL;
@@ -0,0 +1,5 @@
+-- The resolved parameters a screening ran with (groups, NORAD IDs, window
+-- start/duration, step, miss threshold, Hoots buffer) as JSON, so results
+-- are self-describing. NULL for screenings from before parameters were
+-- configurable: those ran the fixed 24h / 5 min / 10 km / 30 km screen.
+ALTER TABLE conjunction_screenings ADD COLUMN IF NOT EXISTS params JSONB;
@@ -0,0 +1,15 @@
+-- Who started each screening: 'manual' (POST /api/conjunction/start) or
+-- 'schedule' (conjunction_schedule.rs). Every screening before this column
+-- existed was started by hand.
+ALTER TABLE conjunction_screenings ADD COLUMN IF NOT EXISTS triggered_by TEXT NOT NULL DEFAULT 'manual';
+
+-- One row per schedule bucket (the cadence-aligned start time), same
+-- INSERT ON CONFLICT DO NOTHING race as tle_refresh_claims and
+-- cluster_audit_claims — only the replica that wins the race starts that
+-- bucket's screening and runs the retention prune.
+CREATE TABLE IF NOT EXISTS conjunction_schedule_claims (
+    bucket TIMESTAMPTZ PRIMARY KEY
+);
+
+-- The retention prune deletes by age.
+CREATE INDEX IF NOT EXISTS idx_conjunction_screenings_started_at ON conjunction_screenings (started_at);
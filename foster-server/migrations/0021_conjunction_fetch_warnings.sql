@@ -0,0 +1,5 @@
+-- TLE sources (groups or individual NORAD IDs) that failed or came back
+-- partial while a screening's catalog was fetched, as a JSON array of
+-- messages. The screening ran on whatever the other sources returned; a
+-- fetch where every source failed fails the screening instead.
+ALTER TABLE conjunction_screenings ADD COLUMN IF NOT EXISTS fetch_warnings JSONB;
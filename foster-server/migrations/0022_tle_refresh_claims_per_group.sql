@@ -0,0 +1,8 @@
+-- tle_cache now holds every TLE source the conjunction and satellites
+-- modules read (CelesTrak groups, and single catalog numbers as
+-- 'catnr-<id>'), not just 'active', so the refresh race is per source: one
+-- replica refetches each stale source per minute bucket. Existing claims
+-- were all for 'active'.
+ALTER TABLE tle_refresh_claims ADD COLUMN IF NOT EXISTS group_name TEXT NOT NULL DEFAULT 'active';
+ALTER TABLE tle_refresh_claims DROP CONSTRAINT IF EXISTS tle_refresh_claims_pkey;
+ALTER TABLE tle_refresh_claims ADD PRIMARY KEY (group_name, bucket);
@@ -0,0 +1,11 @@
+-- In-flight progress of a running chunk, written by its claimant on every
+-- heartbeat, so any replica can stream a screening's progress: anchors (or
+-- grid samples) finished, their cost in work units against work_total
+-- (pairs opened per anchor, one per sample — what the ETA is based on),
+-- and pairs past Hoots / events found so far. Chunks enqueued before these
+-- columns existed have work_total 0 and report no progress until complete.
+ALTER TABLE conjunction_chunks ADD COLUMN IF NOT EXISTS work_total BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE conjunction_chunks ADD COLUMN IF NOT EXISTS progress_units BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE conjunction_chunks ADD COLUMN IF NOT EXISTS progress_work BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE conjunction_chunks ADD COLUMN IF NOT EXISTS progress_pairs BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE conjunction_chunks ADD COLUMN IF NOT EXISTS progress_events BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,23 @@
+-- Which objects an event is between, beyond their names (which CelesTrak
+-- doesn't keep unique): NORAD catalog numbers — the key the satellites
+-- view uses — COSPAR international designators, and how old each object's
+-- TLE epoch was at the start of the screening window, in days.
+ALTER TABLE conjunction_events ADD COLUMN IF NOT EXISTS sat_a_norad_id INTEGER;
+ALTER TABLE conjunction_events ADD COLUMN IF NOT EXISTS sat_b_norad_id INTEGER;
+ALTER TABLE conjunction_events ADD COLUMN IF NOT EXISTS sat_a_designator TEXT;
+ALTER TABLE conjunction_events ADD COLUMN IF NOT EXISTS sat_b_designator TEXT;
+ALTER TABLE conjunction_events ADD COLUMN IF NOT EXISTS sat_a_tle_age_days REAL;
+ALTER TABLE conjunction_events ADD COLUMN IF NOT EXISTS sat_b_tle_age_days REAL;
+
+-- Existing events get their catalog numbers back out of the screening's
+-- tle_snapshot (line 1, columns 3-7); designators and ages stay NULL.
+UPDATE conjunction_events e
+SET sat_a_norad_id = CASE WHEN trim(substr(s.tle_snapshot -> e.sat_a_index ->> 1, 3, 5)) ~ '^[0-9]+$'
+                          THEN trim(substr(s.tle_snapshot -> e.sat_a_index ->> 1, 3, 5))::INTEGER END,
+    sat_b_norad_id = CASE WHEN trim(substr(s.tle_snapshot -> e.sat_b_index ->> 1, 3, 5)) ~ '^[0-9]+$'
+                          THEN trim(substr(s.tle_snapshot -> e.sat_b_index ->> 1, 3, 5))::INTEGER END
+FROM conjunction_screenings s
+WHERE s.id = e.screening_id AND e.sat_a_norad_id IS NULL AND e.sat_a_index IS NOT NULL AND s.tle_snapshot IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS conjunction_events_sat_a_norad_id_idx ON conjunction_events (sat_a_norad_id);
+CREATE INDEX IF NOT EXISTS conjunction_events_sat_b_norad_id_idx ON conjunction_events (sat_b_norad_id);
@@ -0,0 +1,14 @@
+-- Pairs thrown out by each Hoots pre-filter stage: the perigee/apogee
+-- altitude band, the orbit-path (geometric distance between the orbits)
+-- and time (both objects near the planes' line of intersection together)
+-- stages. Counted per chunk by its claimant and summed onto the screening
+-- when the last chunk commits; NULL for grid screenings, which have no
+-- pairwise pre-filter, and for screenings finished before the stages were
+-- counted.
+ALTER TABLE conjunction_chunks ADD COLUMN IF NOT EXISTS rejected_altitude BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE conjunction_chunks ADD COLUMN IF NOT EXISTS rejected_orbit_path BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE conjunction_chunks ADD COLUMN IF NOT EXISTS rejected_time BIGINT NOT NULL DEFAULT 0;
+
+ALTER TABLE conjunction_screenings ADD COLUMN IF NOT EXISTS hoots_rejected_altitude BIGINT;
+ALTER TABLE conjunction_screenings ADD COLUMN IF NOT EXISTS hoots_rejected_orbit_path BIGINT;
+ALTER TABLE conjunction_screenings ADD COLUMN IF NOT EXISTS hoots_rejected_time BIGINT;
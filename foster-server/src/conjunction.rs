@@ -1,96 +1,234 @@
-//! Real conjunction screening — Hoots altitude-band pre-filter, true SGP4
-//! propagation, 288-step/5-minute rolling-24h window scan, ternary-search
-//! TCA refinement, rayon-parallelized pair scanning. Ported near-verbatim
-//! from the real `src/components/conjunction.rs`'s `screening` module (the
+//! Real conjunction screening — three-stage Hoots pre-filter (altitude
+//! band here, orbit path and time in `conjunction_hoots.rs`), true SGP4
+//! propagation, sampled window scan (24h at 5-minute steps unless the
+//! request says otherwise — see `conjunction_params.rs`), range-rate
+//! root TCA refinement, rayon-parallelized pair scanning. Ported from the
+//! real `src/components/conjunction.rs`'s `screening` module (the
 //! scientific core is what makes results real, per the migration plan).
+//! SGP4 returns velocity alongside position, so range-rate comes straight
+//! from each state and TCA, miss distance and relative velocity are all
+//! read off the same pair of states at TCA — no finite differencing.
 //!
-//! Deliberately NOT ported: the distributed chunk-claiming work queue
-//! (`conjunction_chunks`, `FOR UPDATE SKIP LOCKED`) that lets multiple
-//! replicas split the N² pair space without double-counting. This is a
-//! single-process screening pass — correct and complete for one replica.
-//! If/when the production Deployment's 3 replicas need to split the work
-//! (rather than only one of them ever handling a screening request), the
-//! chunk-claiming machinery is the piece to port next; it doesn't change
-//! the correctness of the algorithm itself, only horizontal coordination.
+//! Work is split across replicas through the `conjunction_chunks` queue
+//! (migration 0005): whichever pod receives `POST /api/conjunction/start`
+//! fetches the TLEs, snapshots them onto the `conjunction_screenings` row,
+//! and cuts the anchor range into `[sat_start, sat_end)` chunks. Every
+//! replica's `spawn_chunk_worker` loop claims pending chunks with
+//! `FOR UPDATE SKIP LOCKED`, screens its anchors against every later
+//! satellite (so no pair is screened twice), and the replica that commits
+//! the last chunk finishes the parent row. `claimed_at` doubles as a lease
+//! — a claimant heartbeats it while working, and a chunk whose claimant
+//! stopped heartbeating (pod killed mid-chunk) goes back up for grabs.
+//!
+//! Two close-approach searches exist per pair (`ScreeningMode`). The
+//! original `Coarse` one only refines where a 5-minute sample already sits
+//! under the miss threshold — at LEO closing speeds two objects cover
+//! thousands of km between samples, so it misses most real encounters.
+//! `Fine` (the default) brackets every range minimum by a range-rate sign
+//! change between samples, discards the ones that provably can't dip under
+//! the threshold given the step and the maximum closing speed, and solves
+//! range-rate = 0 inside each remaining bracket. `Grid` drops per-pair
+//! propagation altogether for a spatial index over the whole `active`
+//! catalog (see `conjunction_grid.rs`).
+//!
+//! Every event also carries a 2D collision probability from both objects'
+//! states at TCA (see `conjunction_pc.rs`), which ranks encounters far more
+//! honestly than raw miss distance, and its miss vector broken down along
+//! the first object's radial/in-track/cross-track axes with the angle
+//! between the two velocities — radial separation is what actually keeps
+//! two objects apart when in-track timing is uncertain.
+//!
+//! The screening's lifecycle lives entirely in Postgres too: a `running`
+//! row is inserted up front against `idx_conjunction_screenings_one_running`
+//! (migration 0003), so a second start of the same target (its
+//! `target_label`) while one is in flight — on any replica — is refused
+//! rather than kicking off a parallel pass, and every replica's
+//! `GET /api/conjunction` reads the same row. The index is per target, so
+//! different targets could all run at once; the insert also checks that
+//! fewer than `MAX_RUNNING_SCREENINGS` are running, under a transaction
+//! advisory lock so two claims can't both see the last free slot. A
+//! `running` row that stops making progress (its pod died before enqueueing
+//! chunks, or every claimant vanished) is failed after
+//! `SCREENING_STALE_AFTER` so it can't hold the lock forever.
 
+use crate::conjunction_grid;
+use crate::conjunction_hoots::{hoots_filter, orbit_paths, HootsRejections, MeanOrbit, OrbitPath};
+use crate::conjunction_params::{ScreeningParams, StartRequest};
+use crate::conjunction_pc::{collision_probability, ObjectClass};
+use crate::lighthouse_auth::require_lighthouse_auth;
+use crate::tle::{self, TleSource};
 use rayon::prelude::*;
 use serde::Serialize;
 use serde_json::Value;
 use sgp4::{Constants, Elements};
-use sqlx::PgPool;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::SystemTime;
-use tokio::sync::Mutex;
+use std::time::{Duration, SystemTime};
 
-const MU: f64 = 398_600.4418;
+pub(crate) const MU: f64 = 398_600.4418;
 const EARTH_RADIUS: f64 = 6_371.0;
-const J2000_UNIX_MS: f64 = 946_728_000_000.0;
-const STEP_MINUTES: f64 = 5.0;
-const STEPS: usize = 288;
-const STEP_MS: f64 = STEP_MINUTES * 60_000.0;
-const MISS_THRESHOLD_KM: f64 = 10.0;
-const HOOTS_BUFFER_KM: f64 = 30.0;
+pub(crate) const J2000_UNIX_MS: f64 = 946_728_000_000.0;
+/// Upper bound on the closing speed of two Earth orbiters (two LEO objects
+/// meeting head-on close at ~15.8 km/s).
+pub(crate) const MAX_CLOSING_SPEED_KM_S: f64 = 16.0;
+const TCA_TOLERANCE_MS: f64 = 1.0;
+
+/// Which close-approach search a screening runs. Stored on the screening
+/// row so every replica's chunks use the same one.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScreeningMode {
+    Coarse,
+    Fine,
+    Grid,
+}
+
+impl ScreeningMode {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            ScreeningMode::Coarse => "coarse",
+            ScreeningMode::Fine => "fine",
+            ScreeningMode::Grid => "grid",
+        }
+    }
+
+    /// CelesTrak groups the mode screens unless the request names its own.
+    /// Pairwise modes stick to the small curated groups; the grid is what
+    /// makes the whole catalog tractable.
+    pub(crate) fn default_groups(self) -> &'static [&'static str] {
+        match self {
+            ScreeningMode::Coarse | ScreeningMode::Fine => &SCREENING_GROUPS,
+            ScreeningMode::Grid => &["active"],
+        }
+    }
+
+    pub(crate) fn parse(s: &str) -> Option<Self> {
+        match s {
+            "coarse" => Some(ScreeningMode::Coarse),
+            "fine" => Some(ScreeningMode::Fine),
+            "grid" => Some(ScreeningMode::Grid),
+            _ => None,
+        }
+    }
+}
 
 #[derive(Clone, Serialize)]
 #[serde(tag = "status", rename_all = "lowercase")]
 pub enum Screening {
     Idle,
-    Running,
+    /// `id` is the handle for the progress stream and cancel endpoints.
+    Running {
+        id: i64,
+    },
     Complete {
         total_pairs: usize,
         pairs_after_hoots: usize,
+        /// Pairs each pre-filter stage rejected; `None` for grid
+        /// screenings and ones finished before the stages were counted.
+        hoots_rejections: Option<HootsRejections>,
         events_found: usize,
         elapsed_ms: u64,
+        /// What was screened and how; `None` only if the row's stored
+        /// parameters can't be read back.
+        params: Option<ScreeningParams>,
+        /// TLE sources that failed or came back partial; the screening ran
+        /// on whatever the rest returned.
+        fetch_warnings: Vec<String>,
+        /// Events matching the query's filters, of which `events` is one
+        /// page.
+        total: i64,
         events: Vec<ConjunctionEventOut>,
     },
     Failed {
         error: String,
     },
+    Cancelled,
 }
 
 #[derive(Clone, Serialize)]
 pub struct ConjunctionEventOut {
+    /// Row id once stored; the handle for per-event endpoints such as the
+    /// CDM export.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
     pub sat_a: String,
     pub sat_b: String,
+    /// NORAD catalog numbers — names aren't unique, these are, and they're
+    /// what `/api/satellites?event=` highlights by. `None` only for events
+    /// stored before they were recorded.
+    pub sat_a_norad_id: Option<u32>,
+    pub sat_b_norad_id: Option<u32>,
+    /// COSPAR designators (`1998-067A`), where the element set carries one.
+    pub sat_a_designator: Option<String>,
+    pub sat_b_designator: Option<String>,
+    /// Age of each object's TLE epoch at the start of the screening
+    /// window, in days: stale elements make the geometry less certain.
+    pub sat_a_tle_age_days: Option<f32>,
+    pub sat_b_tle_age_days: Option<f32>,
     pub tca_unix_ms: f64,
     pub miss_distance_km: f32,
     pub rel_velocity_km_s: f32,
-}
-
-pub type ScreeningState = Arc<Mutex<Screening>>;
-
-pub fn initial_state() -> ScreeningState {
-    Arc::new(Mutex::new(Screening::Idle))
+    pub collision_probability: f64,
+    /// B's position relative to A at TCA, resolved along A's radial,
+    /// in-track and cross-track axes. `None` for events stored before the
+    /// breakdown existed.
+    pub miss_radial_km: Option<f32>,
+    pub miss_in_track_km: Option<f32>,
+    pub miss_cross_track_km: Option<f32>,
+    /// Angle between the two velocity vectors at TCA: near 0° an overtaking
+    /// approach, near 180° head-on.
+    pub approach_angle_deg: Option<f32>,
+    /// Positions of both objects in the screening's `tle_snapshot`, so the
+    /// exact element sets that were screened can be re-propagated later.
+    #[serde(skip)]
+    pub sat_indices: (usize, usize),
 }
 
 #[derive(Clone)]
 pub struct ConjunctionAppState {
-    pub screening: ScreeningState,
     pub pool: PgPool,
 }
 
-fn altitude_band(line2: &str) -> (f64, f64) {
-    let e: f64 = line2.get(26..33).map(|s| format!("0.{}", s.trim())).and_then(|s| s.parse().ok()).unwrap_or(0.0);
-    let n_rev_per_day: f64 = line2.get(52..63).and_then(|s| s.trim().parse().ok()).unwrap_or(15.0);
-    let n_rad_s = n_rev_per_day * 2.0 * std::f64::consts::PI / 86_400.0;
+/// Perigee and apogee altitude (km) from mean motion (rev/day) and
+/// eccentricity.
+fn altitude_band(eccentricity: f64, mean_motion_rev_per_day: f64) -> (f64, f64) {
+    let n_rad_s = mean_motion_rev_per_day * 2.0 * std::f64::consts::PI / 86_400.0;
     let a = (MU / (n_rad_s * n_rad_s)).cbrt();
-    (a * (1.0 - e) - EARTH_RADIUS, a * (1.0 + e) - EARTH_RADIUS)
+    (a * (1.0 - eccentricity) - EARTH_RADIUS, a * (1.0 + eccentricity) - EARTH_RADIUS)
+}
+
+pub(crate) fn hoots_pass(a: &SatProp, b: &SatProp, buffer_km: f64) -> bool {
+    let (peri_a, apo_a) = a.altitude_band;
+    let (peri_b, apo_b) = b.altitude_band;
+    peri_a <= apo_b + buffer_km && peri_b <= apo_a + buffer_km
 }
 
-fn hoots_pass(line2_a: &str, line2_b: &str) -> bool {
-    if line2_a.len() < 63 || line2_b.len() < 63 {
-        return false;
+/// COSPAR designator in `YYYY-NNNP` form, expanded from TLE line 1 columns
+/// 10–17 (`98067A` → `1998-067A`). `None` for analyst objects that carry
+/// no designator.
+fn designator_from_line1(line1: &str) -> Option<String> {
+    let raw = line1.get(9..17)?.trim();
+    let year: u32 = raw.get(0..2)?.parse().ok()?;
+    let rest = raw.get(2..)?;
+    if rest.len() < 4 {
+        return None;
     }
-    let (peri_a, apo_a) = altitude_band(line2_a);
-    let (peri_b, apo_b) = altitude_band(line2_b);
-    peri_a <= apo_b + HOOTS_BUFFER_KM && peri_b <= apo_a + HOOTS_BUFFER_KM
+    let century = if year < 57 { 2000 } else { 1900 };
+    Some(format!("{}-{rest}", century + year))
 }
 
 pub struct SatProp {
     pub name: String,
     constants: Constants,
     epoch_j2000_years: f64,
-    pub line2: String,
+    norad_id: u32,
+    international_designator: Option<String>,
+    /// Perigee/apogee altitudes for the Hoots pre-filter, worked out once
+    /// rather than per pair.
+    altitude_band: (f64, f64),
+    /// What the orbit-path and time stages start from.
+    mean_orbit: MeanOrbit,
 }
 
 unsafe impl Send for SatProp {}
@@ -99,247 +237,1389 @@ unsafe impl Sync for SatProp {}
 impl SatProp {
     pub fn new(name: &str, line1: &str, line2: &str) -> Option<Self> {
         let elements = Elements::from_tle(Some(name.to_string()), line1.as_bytes(), line2.as_bytes()).ok()?;
-        let constants = Constants::from_elements(&elements).ok()?;
-        Some(Self { name: name.to_string(), constants, epoch_j2000_years: elements.epoch(), line2: line2.to_string() })
+        let designator = designator_from_line1(line1);
+        Self::from_elements(name, &elements, designator)
+    }
+
+    /// From already-parsed elements, e.g. OMM JSON, which carries its
+    /// designator in `YYYY-NNNP` form already.
+    pub fn from_omm(elements: &Elements) -> Option<Self> {
+        let name = elements.object_name.clone().unwrap_or_else(|| format!("NORAD {}", elements.norad_id));
+        Self::from_elements(&name, elements, elements.international_designator.clone().filter(|d| !d.is_empty()))
+    }
+
+    fn from_elements(name: &str, elements: &Elements, international_designator: Option<String>) -> Option<Self> {
+        let constants = Constants::from_elements(elements).ok()?;
+        Some(Self {
+            name: name.to_string(),
+            constants,
+            epoch_j2000_years: elements.epoch(),
+            norad_id: u32::try_from(elements.norad_id).ok()?,
+            international_designator,
+            altitude_band: altitude_band(elements.eccentricity, elements.mean_motion),
+            mean_orbit: MeanOrbit::from_elements(elements, J2000_UNIX_MS + elements.epoch() * 365.25 * 86_400_000.0),
+        })
+    }
+
+    pub fn norad_id(&self) -> u32 {
+        self.norad_id
+    }
+
+    pub fn international_designator(&self) -> Option<&str> {
+        self.international_designator.as_deref()
+    }
+
+    pub(crate) fn mean_orbit(&self) -> MeanOrbit {
+        self.mean_orbit
+    }
+
+    pub fn epoch_unix_ms(&self) -> f64 {
+        J2000_UNIX_MS + self.epoch_j2000_years * 365.25 * 86_400_000.0
     }
 
-    fn eci_pos(&self, time_unix_ms: f64) -> Option<[f64; 3]> {
+    /// TEME position (km) and velocity (km/s) at `time_unix_ms` — one SGP4
+    /// call gives both, so nothing downstream needs to difference positions.
+    pub fn eci_state(&self, time_unix_ms: f64) -> Option<([f64; 3], [f64; 3])> {
         let minutes_j2000 = (time_unix_ms - J2000_UNIX_MS) / 60_000.0;
         let epoch_minutes = self.epoch_j2000_years * 365.25 * 24.0 * 60.0;
         let tsince = minutes_j2000 - epoch_minutes;
-        self.constants.propagate(sgp4::MinutesSinceEpoch(tsince)).ok().map(|p| p.position)
+        self.constants.propagate(sgp4::MinutesSinceEpoch(tsince)).ok().map(|p| (p.position, p.velocity))
     }
 }
 
+/// The event for a pair at `tca_ms`, every geometric quantity taken from
+/// the one SGP4 state per object at that instant; TLE ages are reckoned
+/// from `window_start_ms`.
+pub(crate) fn event_at_tca(pa: &SatProp, pb: &SatProp, tca_ms: f64, window_start_ms: f64) -> Option<ConjunctionEventOut> {
+    let (ra, va) = pa.eci_state(tca_ms)?;
+    let (rb, vb) = pb.eci_state(tca_ms)?;
+    let dr = [rb[0] - ra[0], rb[1] - ra[1], rb[2] - ra[2]];
+    let dv = [vb[0] - va[0], vb[1] - va[1], vb[2] - va[2]];
+    let miss_rtn = rtn_components(&ra, &va, &dr);
+    let cos_angle = dot(&va, &vb) / (dot(&va, &va) * dot(&vb, &vb)).sqrt();
+    Some(ConjunctionEventOut {
+        id: None,
+        sat_a: pa.name.clone(),
+        sat_b: pb.name.clone(),
+        sat_a_norad_id: Some(pa.norad_id),
+        sat_b_norad_id: Some(pb.norad_id),
+        sat_a_designator: pa.international_designator.clone(),
+        sat_b_designator: pb.international_designator.clone(),
+        sat_a_tle_age_days: Some(((window_start_ms - pa.epoch_unix_ms()) / 86_400_000.0) as f32),
+        sat_b_tle_age_days: Some(((window_start_ms - pb.epoch_unix_ms()) / 86_400_000.0) as f32),
+        tca_unix_ms: tca_ms,
+        miss_distance_km: dot(&dr, &dr).sqrt() as f32,
+        rel_velocity_km_s: dot(&dv, &dv).sqrt() as f32,
+        collision_probability: collision_probability(
+            &ra,
+            &va,
+            ObjectClass::from_name(&pa.name),
+            &rb,
+            &vb,
+            ObjectClass::from_name(&pb.name),
+        ),
+        miss_radial_km: Some(miss_rtn[0] as f32),
+        miss_in_track_km: Some(miss_rtn[1] as f32),
+        miss_cross_track_km: Some(miss_rtn[2] as f32),
+        approach_angle_deg: Some(cos_angle.clamp(-1.0, 1.0).acos().to_degrees() as f32),
+        sat_indices: (0, 0),
+    })
+}
+
+/// Range and range-rate sign (`Δr·Δv`) at every step of the window.
+fn sample_range(pa: &SatProp, pb: &SatProp, params: &ScreeningParams) -> Vec<Option<(f64, f64)>> {
+    (0..params.steps())
+        .map(|i| {
+            let (dr, dv) = relative_state(pa, pb, params.start_unix_ms + i as f64 * params.step_ms())?;
+            Some((dot(&dr, &dr).sqrt(), dot(&dr, &dv)))
+        })
+        .collect()
+}
+
+/// `ScreeningMode::Coarse` pair scan: only a sample that is itself under
+/// the miss threshold and a local range minimum gets refined, by solving
+/// range-rate = 0 on whichever side of it range-rate changes sign.
+pub(crate) fn propagate_pair(pa: &SatProp, pb: &SatProp, params: &ScreeningParams) -> Vec<ConjunctionEventOut> {
+    let samples = sample_range(pa, pb, params);
+    let step_ms = params.step_ms();
+    let mut events = Vec::new();
+
+    for i in 1..samples.len().saturating_sub(1) {
+        let (Some((d_prev, f_prev)), Some((d, f)), Some((d_next, f_next))) = (samples[i - 1], samples[i], samples[i + 1])
+        else {
+            continue;
+        };
+        if !(d < params.miss_threshold_km && d <= d_prev && d <= d_next) {
+            continue;
+        }
+        let t = params.start_unix_ms + i as f64 * step_ms;
+        let bracket = if f >= 0.0 { (t - step_ms, t, f_prev, f) } else { (t, t + step_ms, f, f_next) };
+        let (lo, hi, f_lo, f_hi) = bracket;
+        if !(f_lo < 0.0 && f_hi >= 0.0) {
+            continue;
+        }
+        let Some(tca_ms) = find_range_rate_root(pa, pb, lo, hi, f_lo, f_hi) else { continue };
+        events.extend(event_at_tca(pa, pb, tca_ms, params.start_unix_ms));
+    }
+    events
+}
+
+/// B's position and velocity relative to A, from one SGP4 call per object.
+pub(crate) fn relative_state(a: &SatProp, b: &SatProp, time_unix_ms: f64) -> Option<([f64; 3], [f64; 3])> {
+    let (ra, va) = a.eci_state(time_unix_ms)?;
+    let (rb, vb) = b.eci_state(time_unix_ms)?;
+    Some(([rb[0] - ra[0], rb[1] - ra[1], rb[2] - ra[2]], [vb[0] - va[0], vb[1] - va[1], vb[2] - va[2]]))
+}
+
+#[inline]
+pub(crate) fn dot(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
 #[inline]
-fn dist(a: &[f64; 3], b: &[f64; 3]) -> f64 {
-    let dx = a[0] - b[0];
-    let dy = a[1] - b[1];
-    let dz = a[2] - b[2];
-    (dx * dx + dy * dy + dz * dz).sqrt()
-}
-
-fn find_tca(a: &SatProp, b: &SatProp, mut lo: f64, mut hi: f64) -> f64 {
-    for _ in 0..24 {
-        let m1 = lo + (hi - lo) / 3.0;
-        let m2 = hi - (hi - lo) / 3.0;
-        let d1 = match (a.eci_pos(m1), b.eci_pos(m1)) { (Some(pa), Some(pb)) => dist(&pa, &pb), _ => f64::MAX };
-        let d2 = match (a.eci_pos(m2), b.eci_pos(m2)) { (Some(pa), Some(pb)) => dist(&pa, &pb), _ => f64::MAX };
-        if d1 < d2 { hi = m2 } else { lo = m1 }
-    }
-    (lo + hi) / 2.0
-}
-
-fn propagate_pair(pa: &SatProp, pb: &SatProp, now_unix_ms: f64) -> Vec<ConjunctionEventOut> {
-    let mut pos_a = Vec::with_capacity(STEPS);
-    let mut pos_b = Vec::with_capacity(STEPS);
-    for i in 0..STEPS {
-        let t = now_unix_ms + i as f64 * STEP_MS;
-        match (pa.eci_pos(t), pb.eci_pos(t)) {
-            (Some(a), Some(b)) => { pos_a.push(a); pos_b.push(b); }
-            _ => { pos_a.push([f64::MAX, 0.0, 0.0]); pos_b.push([f64::MAX, 0.0, 0.0]); }
+pub(crate) fn cross(a: &[f64; 3], b: &[f64; 3]) -> [f64; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+#[inline]
+pub(crate) fn unit(a: &[f64; 3]) -> [f64; 3] {
+    let n = dot(a, a).sqrt();
+    [a[0] / n, a[1] / n, a[2] / n]
+}
+
+/// Components of `vec` along the radial / in-track / cross-track axes of
+/// the orbit with state `(r, v)` — the frame conjunction geometry is
+/// reported in.
+pub(crate) fn rtn_components(r: &[f64; 3], v: &[f64; 3], vec: &[f64; 3]) -> [f64; 3] {
+    let radial = unit(r);
+    let cross_track = unit(&cross(r, v));
+    let in_track = cross(&cross_track, &radial);
+    [dot(vec, &radial), dot(vec, &in_track), dot(vec, &cross_track)]
+}
+
+/// Illinois-variant regula falsi for the root of range-rate (`Δr·Δv`, which
+/// has range-rate's sign) inside a bracket with `f_lo < 0 <= f_hi`.
+/// Converges superlinearly like the secant method but, unlike it, can never
+/// step outside the bracket onto a neighbouring minimum.
+pub(crate) fn find_range_rate_root(a: &SatProp, b: &SatProp, lo: f64, hi: f64, f_lo: f64, f_hi: f64) -> Option<f64> {
+    range_rate_root(|t| relative_state(a, b, t), lo, hi, f_lo, f_hi)
+}
+
+/// `find_range_rate_root` over any relative-state source, e.g. one object
+/// flown off its element set by a maneuver.
+pub(crate) fn range_rate_root(
+    relative_state: impl Fn(f64) -> Option<([f64; 3], [f64; 3])>,
+    mut lo: f64,
+    mut hi: f64,
+    mut f_lo: f64,
+    mut f_hi: f64,
+) -> Option<f64> {
+    let mut last_side = 0i8;
+    for _ in 0..64 {
+        if hi - lo < TCA_TOLERANCE_MS {
+            break;
+        }
+        let mut t = (lo * f_hi - hi * f_lo) / (f_hi - f_lo);
+        if !(t > lo && t < hi) {
+            t = 0.5 * (lo + hi);
+        }
+        let (dr, dv) = relative_state(t)?;
+        let f = dot(&dr, &dv);
+        if f < 0.0 {
+            lo = t;
+            f_lo = f;
+            if last_side == -1 {
+                f_hi *= 0.5;
+            }
+            last_side = -1;
+        } else {
+            hi = t;
+            f_hi = f;
+            if last_side == 1 {
+                f_lo *= 0.5;
+            }
+            last_side = 1;
         }
     }
-    let dists: Vec<f64> = pos_a.iter().zip(pos_b.iter()).map(|(a, b)| dist(a, b)).collect();
+    Some(0.5 * (lo + hi))
+}
+
+/// `ScreeningMode::Fine` pair scan: every sample interval over which
+/// range-rate turns from negative to non-negative holds a range minimum.
+/// Within one step of length `h` range can change by at most
+/// `MAX_CLOSING_SPEED_KM_S * h`, so the minimum can't fall below
+/// `(d0 + d1 - v·h) / 2`; only intervals where that bound is under the miss
+/// threshold get refined.
+pub(crate) fn propagate_pair_fine(pa: &SatProp, pb: &SatProp, params: &ScreeningParams) -> Vec<ConjunctionEventOut> {
+    let samples = sample_range(pa, pb, params);
+    let step_ms = params.step_ms();
+    let threshold_km = params.miss_threshold_km;
+    let max_travel_km = MAX_CLOSING_SPEED_KM_S * step_ms / 1000.0;
     let mut events = Vec::new();
 
-    for i in 1..dists.len().saturating_sub(1) {
-        if dists[i] < MISS_THRESHOLD_KM && dists[i] <= dists[i - 1] && dists[i] <= dists[i + 1] {
-            let t_lo = now_unix_ms + (i as f64 - 1.0) * STEP_MS;
-            let t_hi = now_unix_ms + (i as f64 + 1.0) * STEP_MS;
-            let tca_ms = find_tca(pa, pb, t_lo, t_hi);
-            let (tca_a, tca_b) = match (pa.eci_pos(tca_ms), pb.eci_pos(tca_ms)) { (Some(a), Some(b)) => (a, b), _ => continue };
-            let miss_km = dist(&tca_a, &tca_b) as f32;
-
-            const DT_MS: f64 = 30_000.0;
-            let rel_vel = match (pa.eci_pos(tca_ms + DT_MS), pb.eci_pos(tca_ms + DT_MS), pa.eci_pos(tca_ms - DT_MS), pb.eci_pos(tca_ms - DT_MS)) {
-                (Some(a1), Some(b1), Some(a0), Some(b0)) => {
-                    let vx = ((a1[0] - b1[0]) - (a0[0] - b0[0])) / (2.0 * DT_MS / 1000.0);
-                    let vy = ((a1[1] - b1[1]) - (a0[1] - b0[1])) / (2.0 * DT_MS / 1000.0);
-                    let vz = ((a1[2] - b1[2]) - (a0[2] - b0[2])) / (2.0 * DT_MS / 1000.0);
-                    (vx * vx + vy * vy + vz * vz).sqrt() as f32
+    for i in 0..samples.len().saturating_sub(1) {
+        let (Some((d0, f0)), Some((d1, f1))) = (samples[i], samples[i + 1]) else { continue };
+        if !(f0 < 0.0 && f1 >= 0.0) || (d0 + d1 - max_travel_km) / 2.0 >= threshold_km {
+            continue;
+        }
+        let t0 = params.start_unix_ms + i as f64 * step_ms;
+        let Some(tca_ms) = find_range_rate_root(pa, pb, t0, t0 + step_ms, f0, f1) else { continue };
+        let Some(event) = event_at_tca(pa, pb, tca_ms, params.start_unix_ms) else { continue };
+        if f64::from(event.miss_distance_km) < threshold_km {
+            events.push(event);
+        }
+    }
+    events
+}
+
+/// The requested groups, then any requested NORAD IDs those didn't
+/// already include, each read from the shared TLE catalog (`tle.rs`), so a
+/// screening only waits on CelesTrak for a source nobody has cached. Up to
+/// `TLE_LOAD_CONCURRENCY` sources load at once, and all of them within
+/// `TLE_LOAD_DEADLINE`, so even a full 8-group, 100-ID request with
+/// nothing cached is done well inside `SCREENING_STALE_AFTER` — whatever
+/// hasn't loaded by then just becomes a warning. A source that fails, runs past the
+/// deadline, drops sets for bad checksums or is served from a stale cache
+/// is recorded as a warning and the rest still screened; only when nothing
+/// at all comes back is it an error — the screening fails rather than
+/// reporting an empty sky.
+async fn load_screening_tles(pool: &PgPool, params: &ScreeningParams) -> Result<(Vec<tle::Tle>, Vec<String>), String> {
+    let deadline = tokio::time::Instant::now() + TLE_LOAD_DEADLINE;
+    let mut tles: Vec<tle::Tle> = Vec::new();
+    let mut warnings = Vec::new();
+    let groups = params.groups.iter().map(|g| TleSource::group(g)).collect();
+    load_catalogs(pool, groups, deadline, &mut tles, &mut warnings).await;
+    let catnr = |line1: &str| line1.get(2..7).and_then(|s| s.trim().parse::<u32>().ok());
+    let have: std::collections::HashSet<u32> = tles.iter().filter_map(|(_, l1, _)| catnr(l1)).collect();
+    let ids = params.norad_ids.iter().filter(|id| !have.contains(id)).map(|&id| TleSource::Catnr(id)).collect();
+    load_catalogs(pool, ids, deadline, &mut tles, &mut warnings).await;
+    if tles.is_empty() {
+        return Err(warnings.join("; "));
+    }
+    Ok((tles, warnings))
+}
+
+/// Loads `sources` concurrently, appending their sets in `sources` order.
+async fn load_catalogs(
+    pool: &PgPool,
+    sources: Vec<TleSource>,
+    deadline: tokio::time::Instant,
+    tles: &mut Vec<tle::Tle>,
+    warnings: &mut Vec<String>,
+) {
+    use futures_util::StreamExt;
+    let mut loads = futures_util::stream::iter(sources)
+        .map(|source| async move {
+            let result = tokio::time::timeout_at(deadline, tle::get_catalog(Some(pool), &source)).await;
+            (source, result)
+        })
+        .buffered(TLE_LOAD_CONCURRENCY);
+    while let Some((source, result)) = loads.next().await {
+        match result {
+            Ok(Ok(catalog)) => {
+                if catalog.bad_checksums > 0 {
+                    warnings.push(format!("{}: {} element sets failed their checksum", source.query(), catalog.bad_checksums));
                 }
-                _ => 0.0,
-            };
+                if catalog.is_stale() {
+                    warnings.push(format!("{}: using element sets cached at {}, past the catalog TTL", source.query(), catalog.fetched_at));
+                }
+                tles.extend(catalog.tles);
+            }
+            Ok(Err(e)) => warnings.push(e.to_string()),
+            Err(_) => warnings.push(format!("{}: not loaded within {}s", source.query(), TLE_LOAD_DEADLINE.as_secs())),
+        }
+    }
+}
+
+const SCREENING_GROUPS: [&str; 3] = ["stations", "gps-ops", "geo"];
+/// Chunks per screening — a few per replica, so a slow or dying pod only
+/// holds up a small slice of the pair space.
+const CHUNKS_PER_SCREENING: usize = 24;
+/// How long a claimed chunk stays owned without a heartbeat before another
+/// replica may reclaim it.
+const CHUNK_LEASE: Duration = Duration::from_secs(120);
+/// Also how often a running chunk's progress reaches Postgres (and so the
+/// progress stream), and how quickly it notices it was cancelled.
+const CHUNK_HEARTBEAT: Duration = Duration::from_secs(5);
+const CHUNK_POLL: Duration = Duration::from_secs(2);
+/// Events `GET /api/conjunction` returns at most, however many the screening
+/// found — a grid run of the whole catalog can find thousands. The history
+/// endpoints page through the rest.
+const DEFAULT_EVENTS_PAGE: i64 = 50;
+/// Screenings running at once across all targets. Every replica's workers
+/// share one chunk queue, so each extra screening slows all the others.
+const MAX_RUNNING_SCREENINGS: i64 = 2;
+/// A `running` screening with no chunk claimed, heartbeated or completed
+/// for this long is presumed orphaned. Comfortably longer than
+/// `TLE_LOAD_DEADLINE` plus a few `CHUNK_HEARTBEAT`s.
+const SCREENING_STALE_AFTER: Duration = Duration::from_secs(10 * 60);
+/// The whole catalog load for a screening, however many sources it has.
+/// One that has to go to CelesTrak can take a 90s fetch plus another 90s
+/// waiting on a refresh elsewhere, so this leaves room for one round.
+const TLE_LOAD_DEADLINE: Duration = Duration::from_secs(4 * 60);
+/// Sources loaded at once. A cached one comes straight out of Postgres, so
+/// this only really paces the CelesTrak fetches — two at a time, since
+/// CelesTrak throttles clients that hammer it.
+const TLE_LOAD_CONCURRENCY: usize = 2;
+
+fn pod_name() -> String {
+    std::env::var("HOSTNAME").unwrap_or_else(|_| "local-dev".to_string())
+}
+
+fn build_props(tles: &[(String, String, String)]) -> Vec<Option<SatProp>> {
+    tles.iter().map(|(n, l1, l2)| SatProp::new(n, l1, l2)).collect()
+}
+
+/// Splits samples `0..steps` into `chunks` near-equal contiguous ranges.
+fn step_ranges(steps: usize, chunks: usize) -> Vec<(usize, usize)> {
+    let size = steps.div_ceil(chunks.max(1)).max(1);
+    (0..steps).step_by(size).map(|start| (start, (start + size).min(steps))).collect()
+}
 
-            events.push(ConjunctionEventOut {
-                sat_a: pa.name.clone(), sat_b: pb.name.clone(),
-                tca_unix_ms: tca_ms, miss_distance_km: miss_km, rel_velocity_km_s: rel_vel,
-            });
+/// Splits anchors `0..n` into contiguous `[sat_start, sat_end)` ranges of
+/// roughly equal pair count. Anchor `i` owns the `n - i - 1` pairs `(i, j)`
+/// with `j > i`, so an even split by anchor count would front-load almost
+/// all the work onto the first few chunks.
+fn chunk_ranges(n: usize, chunks: usize) -> Vec<(usize, usize)> {
+    let total_pairs: usize = (0..n).map(|i| n - i - 1).sum();
+    let target = total_pairs.div_ceil(chunks.max(1)).max(1);
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    let mut acc = 0;
+    for i in 0..n {
+        acc += n - i - 1;
+        if acc >= target {
+            ranges.push((start, i + 1));
+            start = i + 1;
+            acc = 0;
         }
     }
-    events
+    if start < n {
+        ranges.push((start, n));
+    }
+    ranges
 }
 
-fn fetch_tle_group_blocking(group: &str) -> Vec<(String, String, String)> {
-    let url = format!("https://celestrak.org/NORAD/elements/gp.php?GROUP={group}&FORMAT=tle");
-    let body = reqwest::blocking::Client::builder()
-        .user_agent("Mozilla/5.0 (compatible; jaydanhoward-foster-migration)")
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .and_then(|c| c.get(&url).send())
-        .and_then(|r| r.text())
-        .unwrap_or_default();
-    body.lines()
-        .collect::<Vec<_>>()
-        .chunks(3)
-        .filter(|c| c.len() == 3)
-        .map(|c| (c[0].trim().to_string(), c[1].to_string(), c[2].to_string()))
-        .collect()
+/// Live counters for the chunk a worker is screening, read by its
+/// heartbeat. `units` are anchors (or grid samples) finished; `work` weighs
+/// each by its cost — an anchor by the pairs it opens, a sample as one —
+/// so it can be set against the chunk's `work_total` for an ETA.
+#[derive(Default)]
+pub(crate) struct ChunkProgress {
+    units: AtomicU64,
+    work: AtomicU64,
+    pairs_after_hoots: AtomicU64,
+    events: AtomicU64,
+    /// Set once the chunk turns out to have been cancelled; the remaining
+    /// anchors or samples are skipped.
+    pub(crate) cancelled: AtomicBool,
 }
 
-/// Real screening pass: fetches real TLEs (stations + gps-ops + geo — same
-/// groups the Satellites section uses), applies the Hoots pre-filter, then
-/// rayon-parallel SGP4-propagates every surviving pair over a real 24h/5min
-/// window with ternary-search TCA refinement. Persists to the real
-/// conjunction_screenings/conjunction_events tables (single-process; see
-/// module doc comment on the deferred distributed chunk-claiming).
-fn run_screening_blocking(pool: PgPool) -> Screening {
-    let started = SystemTime::now();
+impl ChunkProgress {
+    pub(crate) fn record(&self, work: u64, pairs_after_hoots: usize, events: usize) {
+        self.units.fetch_add(1, Ordering::Relaxed);
+        self.work.fetch_add(work, Ordering::Relaxed);
+        self.pairs_after_hoots.fetch_add(pairs_after_hoots as u64, Ordering::Relaxed);
+        self.events.fetch_add(events as u64, Ordering::Relaxed);
+    }
 
-    let tles: Vec<(String, String, String)> = ["stations", "gps-ops", "geo"]
-        .iter()
-        .flat_map(|g| fetch_tle_group_blocking(g))
-        .collect();
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
 
-    let props: Vec<Option<SatProp>> = tles.iter().map(|(n, l1, l2)| SatProp::new(n, l1, l2)).collect();
-    let n = props.len();
-    let total_pairs: usize = (0..n).map(|i| n.saturating_sub(i + 1)).sum();
-    let now_unix_ms = started.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis() as f64;
+/// Cost of a chunk in `ChunkProgress::work` units.
+fn chunk_work(mode: ScreeningMode, n: usize, start: usize, end: usize) -> u64 {
+    match mode {
+        ScreeningMode::Grid => (end - start) as u64,
+        _ => (start..end.min(n)).map(|i| (n - i - 1) as u64).sum(),
+    }
+}
 
+/// Screens one chunk's `[start, end)` range — anchors for the pairwise
+/// modes, window samples for `Grid`.
+fn screen_chunk(
+    props: &[Option<SatProp>],
+    paths: &[Option<OrbitPath>],
+    start: usize,
+    end: usize,
+    params: &ScreeningParams,
+    mode: ScreeningMode,
+    progress: &ChunkProgress,
+) -> (usize, HootsRejections, Vec<ConjunctionEventOut>) {
+    match mode {
+        ScreeningMode::Coarse => screen_anchor_range(props, paths, start, end, params, propagate_pair, progress),
+        ScreeningMode::Fine => screen_anchor_range(props, paths, start, end, params, propagate_pair_fine, progress),
+        // The grid has no pairwise pre-filter to count.
+        ScreeningMode::Grid => {
+            let (pairs, events) = conjunction_grid::screen_step_range(props, start, end, params, progress);
+            (pairs, HootsRejections::default(), events)
+        }
+    }
+}
+
+/// Screens every anchor in `[sat_start, sat_end)` against every later
+/// satellite: the three Hoots stages (`conjunction_hoots.rs`, on `paths`
+/// from `orbit_paths`), then full propagation of each survivor. Returns the
+/// surviving pair count and each stage's rejections alongside the events
+/// found.
+fn screen_anchor_range(
+    props: &[Option<SatProp>],
+    paths: &[Option<OrbitPath>],
+    sat_start: usize,
+    sat_end: usize,
+    params: &ScreeningParams,
+    scan_pair: fn(&SatProp, &SatProp, &ScreeningParams) -> Vec<ConjunctionEventOut>,
+    progress: &ChunkProgress,
+) -> (usize, HootsRejections, Vec<ConjunctionEventOut>) {
+    let n = props.len();
     // Single pass per anchor: count Hoots-surviving pairs and collect any
     // real close-approach events from those pairs, together.
-    let per_anchor: Vec<(usize, Vec<ConjunctionEventOut>)> = (0..n)
+    let per_anchor: Vec<(usize, HootsRejections, Vec<ConjunctionEventOut>)> = (sat_start..sat_end.min(n))
         .into_par_iter()
         .map(|i| {
-            let Some(pa) = &props[i] else { return (0, Vec::new()) };
+            let mut rejections = HootsRejections::default();
+            let Some(pa) = props[i].as_ref().filter(|_| !progress.is_cancelled()) else { return (0, rejections, Vec::new()) };
             let mut hoots_count = 0usize;
             let mut events = Vec::new();
-            for j in (i + 1)..n {
-                let Some(pb) = &props[j] else { continue };
-                if !hoots_pass(&pa.line2, &pb.line2) {
+            for (j, pb) in props.iter().enumerate().skip(i + 1) {
+                let Some(pb) = pb else { continue };
+                if let Some(stage) = hoots_filter(pa, paths[i].as_ref(), pb, paths[j].as_ref(), params) {
+                    rejections.count(stage);
                     continue;
                 }
                 hoots_count += 1;
-                events.extend(propagate_pair(pa, pb, now_unix_ms));
+                events.extend(scan_pair(pa, pb, params).into_iter().map(|e| ConjunctionEventOut { sat_indices: (i, j), ..e }));
             }
-            (hoots_count, events)
+            progress.record((n - i - 1) as u64, hoots_count, events.len());
+            (hoots_count, rejections, events)
         })
         .collect();
 
-    let pairs_after_hoots: usize = per_anchor.iter().map(|(c, _)| c).sum();
-    let events: Vec<ConjunctionEventOut> = per_anchor.into_iter().flat_map(|(_, e)| e).collect();
-
-    let elapsed_ms = started.elapsed().unwrap_or_default().as_millis() as u64;
-
-    // Persist to the real schema (blocking call already off the async
-    // runtime worker thread here, so a small nested block_on is fine —
-    // same reasoning as cluster.rs/visitors.rs).
-    let events_for_db = events.clone();
-    tokio::task::block_in_place(|| {
-        tokio::runtime::Handle::current().block_on(async {
-            let hostname = std::env::var("HOSTNAME").unwrap_or_else(|_| "local-dev".to_string());
-            let screening_id: Result<i64, sqlx::Error> = sqlx::query_scalar(
-                "INSERT INTO conjunction_screenings (group_name, status, completed_at, total_pairs, pairs_after_hoots, events_found, elapsed_ms, calculated_by)
-                 VALUES ($1, 'complete', NOW(), $2, $3, $4, $5, $6) RETURNING id",
-            )
-            .bind("stations+gps-ops+geo")
-            .bind(total_pairs as i64)
-            .bind(pairs_after_hoots as i64)
-            .bind(events_for_db.len() as i32)
-            .bind(elapsed_ms as i64)
-            .bind(&hostname)
-            .fetch_one(&pool)
-            .await;
-
-            if let Ok(id) = screening_id {
-                for e in &events_for_db {
-                    let _ = sqlx::query(
-                        "INSERT INTO conjunction_events (screening_id, sat_a, sat_b, tca_unix_ms, miss_distance_km, rel_velocity_km_s)
-                         VALUES ($1, $2, $3, $4, $5, $6)",
-                    )
-                    .bind(id)
-                    .bind(&e.sat_a)
-                    .bind(&e.sat_b)
-                    .bind(e.tca_unix_ms)
-                    .bind(e.miss_distance_km)
-                    .bind(e.rel_velocity_km_s)
-                    .execute(&pool)
-                    .await;
-                }
-            }
-        })
-    });
+    let pairs_after_hoots = per_anchor.iter().map(|(c, _, _)| c).sum();
+    let mut rejections = HootsRejections::default();
+    for (_, r, _) in &per_anchor {
+        rejections.add(r);
+    }
+    let events = per_anchor.into_iter().flat_map(|(_, _, e)| e).collect();
+    (pairs_after_hoots, rejections, events)
+}
 
-    Screening::Complete { total_pairs, pairs_after_hoots, events_found: events.len(), elapsed_ms, events }
+/// Takes the group's distributed lock by inserting its `running` row. Returns
+/// `None` if another screening of the group is already running (on any
+/// replica) — `ON CONFLICT` against the partial unique index is the lock —
+/// or `MAX_RUNNING_SCREENINGS` of any target are.
+async fn claim_screening(
+    pool: &PgPool,
+    params: &ScreeningParams,
+    mode: ScreeningMode,
+    trigger: &str,
+) -> Result<Option<i64>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext('conjunction_screenings_claim'))").execute(&mut *tx).await?;
+    let id = sqlx::query_scalar(
+        "INSERT INTO conjunction_screenings (group_name, status, calculated_by, mode, params, window_start_unix_ms, triggered_by)
+         SELECT $1, 'running', $2, $3, $4, $5, $6
+         WHERE (SELECT COUNT(*) FROM conjunction_screenings WHERE status = 'running') < $7
+         ON CONFLICT (group_name) WHERE status = 'running' DO NOTHING
+         RETURNING id",
+    )
+    .bind(params.target_label())
+    .bind(pod_name())
+    .bind(mode.as_str())
+    .bind(serde_json::to_value(params).unwrap_or(Value::Null))
+    .bind(params.start_unix_ms)
+    .bind(trigger)
+    .bind(MAX_RUNNING_SCREENINGS)
+    .fetch_optional(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(id)
 }
 
-pub async fn get_screening(state: axum::extract::State<ConjunctionAppState>) -> axum::Json<Value> {
-    let current = state.0.screening.lock().await.clone();
-    // On a fresh process (nothing screened yet this run), fall back to the
-    // last real completed screening from the DB rather than always
-    // reporting "idle" until someone clicks the button again.
-    if matches!(current, Screening::Idle) {
-        let from_db = latest_from_db(&state.0.pool).await;
-        if from_db["status"] == "complete" {
-            return axum::Json(from_db);
+/// Claims the target's lock and, if this call got it, enqueues the chunks
+/// in the background. `Ok(None)` when the same target is already running,
+/// or too many others are.
+/// `trigger` records who asked: `manual` (the page's default screen),
+/// `custom` (an authenticated start with a body) or `schedule`.
+pub(crate) async fn launch_screening(
+    pool: &PgPool,
+    mode: ScreeningMode,
+    params: ScreeningParams,
+    trigger: &str,
+) -> Result<Option<i64>, sqlx::Error> {
+    expire_stale_screenings(pool).await;
+    let Some(screening_id) = claim_screening(pool, &params, mode, trigger).await? else {
+        return Ok(None);
+    };
+    let pool = pool.clone();
+    tokio::spawn(async move {
+        if let Err(error) = enqueue_chunks(&pool, screening_id, mode, params).await {
+            fail_screening(&pool, screening_id, &error).await;
         }
+    });
+    Ok(Some(screening_id))
+}
+
+/// Coordinator half of a screening: loads real TLEs (the requested groups
+/// and NORAD IDs) from the shared catalog and snapshots them onto the
+/// claimed `conjunction_screenings` row, which already holds the parameters
+/// (so every replica screens the exact same array over the exact same
+/// window), and enqueues the chunks.
+async fn enqueue_chunks(pool: &PgPool, screening_id: i64, mode: ScreeningMode, params: ScreeningParams) -> Result<(), String> {
+    let grid_steps = conjunction_grid::grid_steps(&params);
+    let (tles, fetch_warnings) = load_screening_tles(pool, &params).await?;
+    for warning in &fetch_warnings {
+        eprintln!("Conjunction screening {screening_id}: {warning}");
+    }
+
+    let n = tles.len();
+    let total_pairs: usize = (0..n).map(|i| n - i - 1).sum();
+    let ranges = match mode {
+        // Every sample costs the same (one propagation of the whole
+        // catalog), so the window splits evenly — unless there's nothing
+        // to screen.
+        ScreeningMode::Grid if n >= 2 => step_ranges(grid_steps, CHUNKS_PER_SCREENING),
+        ScreeningMode::Grid => Vec::new(),
+        _ => chunk_ranges(n, CHUNKS_PER_SCREENING),
+    };
+    let group_name: String = sqlx::query_scalar("SELECT group_name FROM conjunction_screenings WHERE id = $1")
+        .bind(screening_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let still_running = sqlx::query(
+        "UPDATE conjunction_screenings SET total_pairs = $2, tle_snapshot = $3, fetch_warnings = $4
+         WHERE id = $1 AND status = 'running'",
+    )
+    .bind(screening_id)
+    .bind(total_pairs as i64)
+    .bind(serde_json::to_value(&tles).unwrap_or(Value::Null))
+    .bind(serde_json::to_value(&fetch_warnings).unwrap_or(Value::Null))
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .rows_affected()
+        == 1;
+    // Cancelled (or expired) while the TLEs were loading.
+    if !still_running {
+        return tx.rollback().await.map_err(|e| e.to_string());
+    }
+
+    for (chunk_idx, (sat_start, sat_end)) in ranges.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO conjunction_chunks (screening_id, group_name, chunk_idx, sat_start, sat_end, work_total)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(screening_id)
+        .bind(&group_name)
+        .bind(chunk_idx as i32)
+        .bind(*sat_start as i32)
+        .bind(*sat_end as i32)
+        .bind(chunk_work(mode, n, *sat_start, *sat_end) as i64)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    // Nothing to split (a grid screening of a single object) means no
+    // chunk will ever complete and finish the row, so finish it here.
+    if ranges.is_empty() {
+        sqlx::query("UPDATE conjunction_screenings SET status = 'complete', completed_at = NOW() WHERE id = $1")
+            .bind(screening_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
     }
-    axum::Json(serde_json::to_value(current).unwrap_or(serde_json::json!({"status":"idle"})))
+
+    tx.commit().await.map_err(|e| e.to_string())
 }
 
-pub async fn start_screening(state: axum::extract::State<ConjunctionAppState>) -> axum::http::StatusCode {
-    {
-        let mut s = state.0.screening.lock().await;
-        *s = Screening::Running;
+async fn fail_screening(pool: &PgPool, screening_id: i64, error: &str) {
+    let _ = sqlx::query(
+        "UPDATE conjunction_screenings SET status = 'failed', completed_at = NOW(), error_msg = $2
+         WHERE id = $1 AND status = 'running'",
+    )
+    .bind(screening_id)
+    .bind(error)
+    .execute(pool)
+    .await;
+}
+
+/// Fails every `running` screening that hasn't shown any sign of life —
+/// row creation, or a chunk claimed, heartbeated or completed — within
+/// `SCREENING_STALE_AFTER`, releasing the group's lock. Its leftover chunks
+/// are failed with it so no worker picks them up later.
+pub(crate) async fn expire_stale_screenings(pool: &PgPool) {
+    let _ = sqlx::query(
+        "WITH stale AS (
+             UPDATE conjunction_screenings s
+             SET status = 'failed', completed_at = NOW(), error_msg = 'no progress for ' || $1::BIGINT || 's; presumed orphaned'
+             WHERE s.status = 'running'
+               AND GREATEST(s.started_at, (SELECT MAX(GREATEST(c.claimed_at, c.completed_at))
+                                           FROM conjunction_chunks c WHERE c.screening_id = s.id))
+                   < NOW() - make_interval(secs => $1::BIGINT)
+             RETURNING s.id
+         )
+         UPDATE conjunction_chunks SET status = 'failed'
+         WHERE screening_id IN (SELECT id FROM stale) AND status IN ('pending', 'running')",
+    )
+    .bind(SCREENING_STALE_AFTER.as_secs() as i64)
+    .execute(pool)
+    .await;
+}
+
+/// A screening's parsed TLE snapshot and search settings, kept by a chunk
+/// worker between chunks of the same screening. `paths` are the Hoots
+/// stages' per-object orbits over the window (empty for `Grid`, which
+/// doesn't use them).
+struct LoadedScreening {
+    id: i64,
+    props: Arc<Vec<Option<SatProp>>>,
+    paths: Arc<Vec<Option<OrbitPath>>>,
+    params: Arc<ScreeningParams>,
+    mode: ScreeningMode,
+}
+
+struct ClaimedChunk {
+    id: i64,
+    screening_id: i64,
+    sat_start: usize,
+    sat_end: usize,
+}
+
+/// Claims the oldest pending chunk of any running screening — or one whose
+/// claimant's lease has lapsed — with `FOR UPDATE SKIP LOCKED`, so
+/// concurrent replicas never claim the same chunk.
+async fn claim_next_chunk(pool: &PgPool, pod: &str) -> Option<ClaimedChunk> {
+    let row: (i64, i64, i32, i32) = sqlx::query_as(
+        "UPDATE conjunction_chunks SET status = 'running', claimed_by = $1, claimed_at = NOW()
+         WHERE id = (
+             SELECT c.id FROM conjunction_chunks c
+             JOIN conjunction_screenings s ON s.id = c.screening_id
+             WHERE s.status = 'running'
+               AND (c.status = 'pending'
+                    OR (c.status = 'running' AND c.claimed_at < NOW() - make_interval(secs => $2)))
+             ORDER BY c.screening_id, c.chunk_idx
+             LIMIT 1
+             FOR UPDATE OF c SKIP LOCKED
+         )
+         RETURNING id, screening_id, sat_start, sat_end",
+    )
+    .bind(pod)
+    .bind(CHUNK_LEASE.as_secs_f64())
+    .fetch_optional(pool)
+    .await
+    .ok()
+    .flatten()?;
+    Some(ClaimedChunk { id: row.0, screening_id: row.1, sat_start: row.2.max(0) as usize, sat_end: row.3.max(0) as usize })
+}
+
+/// Renews the chunk's lease and records its progress so far. Returns
+/// false once the chunk is no longer this pod's running chunk — cancelled,
+/// or reclaimed after a lapsed lease — so the work can stop. A database
+/// error isn't taken as that.
+async fn heartbeat_chunk(pool: &PgPool, chunk_id: i64, pod: &str, progress: &ChunkProgress) -> bool {
+    let result = sqlx::query(
+        "UPDATE conjunction_chunks
+         SET claimed_at = NOW(), progress_units = $3, progress_work = $4, progress_pairs = $5, progress_events = $6
+         WHERE id = $1 AND claimed_by = $2 AND status = 'running'",
+    )
+    .bind(chunk_id)
+    .bind(pod)
+    .bind(progress.units.load(Ordering::Relaxed) as i64)
+    .bind(progress.work.load(Ordering::Relaxed) as i64)
+    .bind(progress.pairs_after_hoots.load(Ordering::Relaxed) as i64)
+    .bind(progress.events.load(Ordering::Relaxed) as i64)
+    .execute(pool)
+    .await;
+    !matches!(result, Ok(r) if r.rows_affected() == 0)
+}
+
+/// A screening row's parameters. Rows from before parameters were stored
+/// ran the fixed default screen from their window start.
+fn stored_params(params: Option<Value>, window_start_unix_ms: Option<f64>, mode: ScreeningMode) -> Option<ScreeningParams> {
+    match params {
+        Some(params) => serde_json::from_value(params).ok(),
+        None => Some(ScreeningParams::defaults(mode, window_start_unix_ms?)),
+    }
+}
+
+/// A row's `fetch_warnings` column: the sources that failed or came back
+/// short while loading its TLEs. Empty for screenings stored before
+/// warnings were recorded.
+pub(crate) fn row_fetch_warnings(r: &PgRow) -> Vec<String> {
+    r.try_get::<Option<Value>, _>("fetch_warnings")
+        .ok()
+        .flatten()
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// A row's `hoots_rejected_*` columns.
+pub(crate) fn row_hoots_rejections(r: &PgRow) -> Option<HootsRejections> {
+    let column = |name| r.try_get::<Option<i64>, _>(name).ok().flatten().map(|v| v as u64);
+    Some(HootsRejections {
+        altitude: column("hoots_rejected_altitude")?,
+        orbit_path: column("hoots_rejected_orbit_path")?,
+        time: column("hoots_rejected_time")?,
+    })
+}
+
+/// `stored_params` for a row selected with `params`, `window_start_unix_ms`
+/// and `mode` columns.
+pub(crate) fn row_params(r: &PgRow) -> Option<ScreeningParams> {
+    let mode = ScreeningMode::parse(&r.try_get::<String, _>("mode").ok()?)?;
+    stored_params(r.try_get("params").ok().flatten(), r.try_get("window_start_unix_ms").ok().flatten(), mode)
+}
+
+async fn load_screening_snapshot(pool: &PgPool, screening_id: i64) -> Option<LoadedScreening> {
+    let row: (Option<Value>, Option<Value>, Option<f64>, String) = sqlx::query_as(
+        "SELECT tle_snapshot, params, window_start_unix_ms, mode FROM conjunction_screenings WHERE id = $1",
+    )
+    .bind(screening_id)
+    .fetch_optional(pool)
+    .await
+    .ok()
+    .flatten()?;
+    let mode = ScreeningMode::parse(&row.3)?;
+    let params = stored_params(row.1, row.2, mode)?;
+    let tles: Vec<(String, String, String)> = serde_json::from_value(row.0?).ok()?;
+    let (props, paths, params) = tokio::task::spawn_blocking(move || {
+        let props = build_props(&tles);
+        let paths = if mode == ScreeningMode::Grid { Vec::new() } else { orbit_paths(&props, &params) };
+        (props, paths, params)
+    })
+    .await
+    .ok()?;
+    Some(LoadedScreening { id: screening_id, props: Arc::new(props), paths: Arc::new(paths), params: Arc::new(params), mode })
+}
+
+/// Commits a finished chunk's counts and events, then finishes the parent
+/// screening if that was the last outstanding chunk. The parent row is
+/// locked first so two replicas committing the final two chunks at the same
+/// moment can't each see the other's chunk as still running and both skip
+/// finishing it. A chunk that was reclaimed from under this replica (lease
+/// lapsed) is silently dropped instead of double-counting its events.
+async fn complete_chunk(
+    pool: &PgPool,
+    chunk: &ClaimedChunk,
+    pod: &str,
+    pairs_screened: usize,
+    rejections: &HootsRejections,
+    events: &[ConjunctionEventOut],
+    elapsed_ms: u64,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("SELECT id FROM conjunction_screenings WHERE id = $1 FOR UPDATE")
+        .bind(chunk.screening_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let owned = sqlx::query(
+        "UPDATE conjunction_chunks
+         SET status = 'complete', completed_at = NOW(), pairs_screened = $3, events_found = $4, elapsed_ms = $5,
+             rejected_altitude = $6, rejected_orbit_path = $7, rejected_time = $8
+         WHERE id = $1 AND claimed_by = $2 AND status = 'running'",
+    )
+    .bind(chunk.id)
+    .bind(pod)
+    .bind(pairs_screened as i64)
+    .bind(events.len() as i32)
+    .bind(elapsed_ms as i64)
+    .bind(rejections.altitude as i64)
+    .bind(rejections.orbit_path as i64)
+    .bind(rejections.time as i64)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected()
+        == 1;
+    if !owned {
+        return tx.rollback().await;
     }
-    let screening = state.0.screening.clone();
-    let pool = state.0.pool.clone();
+
+    for e in events {
+        sqlx::query(
+            "INSERT INTO conjunction_events (screening_id, sat_a, sat_b, tca_unix_ms, miss_distance_km, rel_velocity_km_s, calculated_by, collision_probability, sat_a_index, sat_b_index,
+                                            miss_radial_km, miss_in_track_km, miss_cross_track_km, approach_angle_deg,
+                                            sat_a_norad_id, sat_b_norad_id, sat_a_designator, sat_b_designator, sat_a_tle_age_days, sat_b_tle_age_days)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)",
+        )
+        .bind(chunk.screening_id)
+        .bind(&e.sat_a)
+        .bind(&e.sat_b)
+        .bind(e.tca_unix_ms)
+        .bind(e.miss_distance_km)
+        .bind(e.rel_velocity_km_s)
+        .bind(pod)
+        .bind(e.collision_probability)
+        .bind(e.sat_indices.0 as i32)
+        .bind(e.sat_indices.1 as i32)
+        .bind(e.miss_radial_km)
+        .bind(e.miss_in_track_km)
+        .bind(e.miss_cross_track_km)
+        .bind(e.approach_angle_deg)
+        .bind(e.sat_a_norad_id.map(|id| id as i32))
+        .bind(e.sat_b_norad_id.map(|id| id as i32))
+        .bind(&e.sat_a_designator)
+        .bind(&e.sat_b_designator)
+        .bind(e.sat_a_tle_age_days)
+        .bind(e.sat_b_tle_age_days)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    sqlx::query(
+        "UPDATE conjunction_screenings s
+         SET status = 'complete', completed_at = NOW(),
+             pairs_after_hoots = c.pairs, events_found = c.events,
+             hoots_rejected_altitude = CASE WHEN s.mode = 'grid' THEN NULL ELSE c.altitude END,
+             hoots_rejected_orbit_path = CASE WHEN s.mode = 'grid' THEN NULL ELSE c.orbit_path END,
+             hoots_rejected_time = CASE WHEN s.mode = 'grid' THEN NULL ELSE c.time END,
+             elapsed_ms = (EXTRACT(EPOCH FROM NOW() - s.started_at) * 1000)::BIGINT
+         FROM (SELECT COALESCE(SUM(pairs_screened), 0)::BIGINT AS pairs, COALESCE(SUM(events_found), 0)::INT AS events,
+                      COALESCE(SUM(rejected_altitude), 0)::BIGINT AS altitude,
+                      COALESCE(SUM(rejected_orbit_path), 0)::BIGINT AS orbit_path,
+                      COALESCE(SUM(rejected_time), 0)::BIGINT AS time
+               FROM conjunction_chunks WHERE screening_id = $1) c
+         WHERE s.id = $1 AND s.status = 'running'
+           AND NOT EXISTS (SELECT 1 FROM conjunction_chunks WHERE screening_id = $1 AND status <> 'complete')",
+    )
+    .bind(chunk.screening_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await
+}
+
+/// A chunk that can't be screened fails its whole screening — the result
+/// would silently be missing that slice of the pair space otherwise.
+async fn fail_chunk(pool: &PgPool, chunk: &ClaimedChunk, pod: &str, error: &str) {
+    let _ = sqlx::query(
+        "WITH failed AS (
+             UPDATE conjunction_chunks SET status = 'failed', completed_at = NOW(), error_msg = $3
+             WHERE id = $1 AND claimed_by = $2 AND status = 'running'
+             RETURNING screening_id
+         ), parent AS (
+             UPDATE conjunction_screenings SET status = 'failed', completed_at = NOW(), error_msg = $3
+             WHERE id IN (SELECT screening_id FROM failed) AND status = 'running'
+         )
+         UPDATE conjunction_chunks SET status = 'failed'
+         WHERE screening_id IN (SELECT screening_id FROM failed) AND status = 'pending'",
+    )
+    .bind(chunk.id)
+    .bind(pod)
+    .bind(error)
+    .execute(pool)
+    .await;
+}
+
+/// Spawns this replica's chunk worker: claims chunks from any running
+/// screening (regardless of which pod started it), screens them on the
+/// rayon pool while heartbeating the lease, and commits the results. Runs
+/// on every replica, so the N² pass is split across all of them.
+pub fn spawn_chunk_worker(pool: PgPool) {
     tokio::spawn(async move {
-        let result = tokio::task::spawn_blocking(move || run_screening_blocking(pool)).await.unwrap_or_else(|e| Screening::Failed { error: e.to_string() });
-        let mut s = screening.lock().await;
-        *s = result;
+        let pod = pod_name();
+        // The TLE snapshot is the same for every chunk of a screening, so
+        // it's parsed (and its orbit paths built) once per screening rather
+        // than once per chunk.
+        let mut loaded: Option<LoadedScreening> = None;
+
+        loop {
+            let Some(chunk) = claim_next_chunk(&pool, &pod).await else {
+                tokio::time::sleep(CHUNK_POLL).await;
+                continue;
+            };
+
+            if loaded.as_ref().map(|l| l.id) != Some(chunk.screening_id) {
+                loaded = load_screening_snapshot(&pool, chunk.screening_id).await;
+            }
+            let Some(screening) = &loaded else {
+                fail_chunk(&pool, &chunk, &pod, "screening TLE snapshot missing").await;
+                continue;
+            };
+            let props = screening.props.clone();
+            let paths = screening.paths.clone();
+            let params = screening.params.clone();
+            let mode = screening.mode;
+
+            let started = SystemTime::now();
+            let (sat_start, sat_end) = (chunk.sat_start, chunk.sat_end);
+            let progress = Arc::new(ChunkProgress::default());
+            let counters = progress.clone();
+            let mut work =
+                tokio::task::spawn_blocking(move || screen_chunk(&props, &paths, sat_start, sat_end, &params, mode, &counters));
+            let mut heartbeat = tokio::time::interval(CHUNK_HEARTBEAT);
+            heartbeat.tick().await;
+            let result = loop {
+                tokio::select! {
+                    r = &mut work => break r,
+                    _ = heartbeat.tick() => {
+                        if !heartbeat_chunk(&pool, chunk.id, &pod, &progress).await {
+                            progress.cancelled.store(true, Ordering::Relaxed);
+                        }
+                    }
+                }
+            };
+            let elapsed_ms = started.elapsed().unwrap_or_default().as_millis() as u64;
+
+            match result {
+                Ok((pairs_screened, rejections, events)) => {
+                    if let Err(e) = complete_chunk(&pool, &chunk, &pod, pairs_screened, &rejections, &events, elapsed_ms).await {
+                        eprintln!("Conjunction chunk {} commit failed: {e}", chunk.id);
+                    }
+                }
+                Err(e) => fail_chunk(&pool, &chunk, &pod, &e.to_string()).await,
+            }
+        }
     });
-    axum::http::StatusCode::ACCEPTED
 }
 
-/// Real, previously-completed screenings from the DB (for initial page load
-/// before anyone clicks "Screen").
-pub async fn latest_from_db(pool: &PgPool) -> Value {
+pub(crate) fn event_from_row(r: &PgRow) -> ConjunctionEventOut {
+    ConjunctionEventOut {
+        id: r.try_get("id").ok(),
+        sat_a: r.try_get("sat_a").unwrap_or_default(),
+        sat_b: r.try_get("sat_b").unwrap_or_default(),
+        sat_a_norad_id: r.try_get::<Option<i32>, _>("sat_a_norad_id").ok().flatten().map(|id| id as u32),
+        sat_b_norad_id: r.try_get::<Option<i32>, _>("sat_b_norad_id").ok().flatten().map(|id| id as u32),
+        sat_a_designator: r.try_get("sat_a_designator").ok().flatten(),
+        sat_b_designator: r.try_get("sat_b_designator").ok().flatten(),
+        sat_a_tle_age_days: r.try_get("sat_a_tle_age_days").ok().flatten(),
+        sat_b_tle_age_days: r.try_get("sat_b_tle_age_days").ok().flatten(),
+        tca_unix_ms: r.try_get("tca_unix_ms").unwrap_or(0.0),
+        miss_distance_km: r.try_get("miss_distance_km").unwrap_or(0.0),
+        rel_velocity_km_s: r.try_get("rel_velocity_km_s").unwrap_or(0.0),
+        // Events stored before Pc existed read back as 0 rather than
+        // pretending to a probability nobody computed.
+        collision_probability: r.try_get::<Option<f64>, _>("collision_probability").ok().flatten().unwrap_or(0.0),
+        miss_radial_km: r.try_get("miss_radial_km").ok().flatten(),
+        miss_in_track_km: r.try_get("miss_in_track_km").ok().flatten(),
+        miss_cross_track_km: r.try_get("miss_cross_track_km").ok().flatten(),
+        approach_angle_deg: r.try_get("approach_angle_deg").ok().flatten(),
+        sat_indices: (0, 0),
+    }
+}
+
+/// Sort and filter for a screening's event list: `sort` is `tca`
+/// (default, soonest first), `miss` (closest first), `radial` (smallest
+/// radial separation first) or `pc` (most probable first); `min_pc` drops
+/// events below that collision probability, `max_miss_km` those farther
+/// apart, `sat` keeps events naming a matching object (case-insensitive
+/// substring), `norad_id` those involving that catalog number and
+/// `tca_from_unix_ms`/`tca_to_unix_ms` bound the TCA. `limit`/`offset` page
+/// through the result.
+#[derive(serde::Deserialize, Default)]
+pub struct EventQuery {
+    sort: Option<String>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+    min_pc: Option<f64>,
+    max_miss_km: Option<f64>,
+    sat: Option<String>,
+    norad_id: Option<u32>,
+    tca_from_unix_ms: Option<f64>,
+    tca_to_unix_ms: Option<f64>,
+}
+
+impl EventQuery {
+    /// Caps `limit` at `max`, defaulting to it, for endpoints that always
+    /// page.
+    pub(crate) fn paged(self, max: i64) -> Self {
+        Self { limit: Some(self.limit.unwrap_or(max).clamp(1, max)), ..self }
+    }
+
+    pub(crate) fn limit(&self) -> Option<i64> {
+        self.limit
+    }
+
+    pub(crate) fn offset(&self) -> i64 {
+        self.offset.unwrap_or(0).max(0)
+    }
+
+    pub(crate) fn order_by(&self) -> Option<&'static str> {
+        match self.sort.as_deref() {
+            None | Some("tca") => Some("tca_unix_ms"),
+            Some("miss") => Some("miss_distance_km, tca_unix_ms"),
+            Some("radial") => Some("ABS(miss_radial_km) NULLS LAST, miss_distance_km"),
+            Some("pc") => Some("collision_probability DESC NULLS LAST, miss_distance_km"),
+            Some(_) => None,
+        }
+    }
+}
+
+/// Loads a screening (and, once complete, its events) back out of Postgres
+/// — chunks may have been committed by any replica, so the database is the
+/// only place the full result exists.
+pub(crate) async fn load_screening(pool: &PgPool, screening_id: i64, query: &EventQuery) -> Option<Screening> {
     let row = sqlx::query(
-        "SELECT id, total_pairs, pairs_after_hoots, events_found, elapsed_ms FROM conjunction_screenings
-         WHERE status = 'complete' ORDER BY id DESC LIMIT 1",
+        "SELECT status, error_msg, total_pairs, pairs_after_hoots, events_found, elapsed_ms,
+                params, window_start_unix_ms, mode, fetch_warnings,
+                hoots_rejected_altitude, hoots_rejected_orbit_path, hoots_rejected_time
+         FROM conjunction_screenings WHERE id = $1",
     )
+    .bind(screening_id)
     .fetch_optional(pool)
     .await
     .ok()
-    .flatten();
-
-    match row {
-        Some(r) => {
-            use sqlx::Row;
-            let id: i64 = r.try_get("id").unwrap_or(0);
-            let events = sqlx::query("SELECT sat_a, sat_b, tca_unix_ms, miss_distance_km, rel_velocity_km_s FROM conjunction_events WHERE screening_id = $1 ORDER BY tca_unix_ms LIMIT 20")
-                .bind(id)
-                .fetch_all(pool)
-                .await
-                .unwrap_or_default();
-            let events: Vec<Value> = events.iter().map(|e| {
-                serde_json::json!({
-                    "sat_a": e.try_get::<String,_>("sat_a").unwrap_or_default(),
-                    "sat_b": e.try_get::<String,_>("sat_b").unwrap_or_default(),
-                    "miss_distance_km": e.try_get::<f32,_>("miss_distance_km").unwrap_or(0.0),
-                })
-            }).collect();
-            serde_json::json!({
-                "status": "complete",
-                "total_pairs": r.try_get::<i64,_>("total_pairs").unwrap_or(0),
-                "pairs_after_hoots": r.try_get::<i64,_>("pairs_after_hoots").unwrap_or(0),
-                "events_found": r.try_get::<i32,_>("events_found").unwrap_or(0),
-                "elapsed_ms": r.try_get::<i64,_>("elapsed_ms").unwrap_or(0),
-                "events": events,
+    .flatten()?;
+
+    match row.try_get::<String, _>("status").ok()?.as_str() {
+        "running" => Some(Screening::Running { id: screening_id }),
+        "cancelled" => Some(Screening::Cancelled),
+        "failed" => Some(Screening::Failed {
+            error: row.try_get::<Option<String>, _>("error_msg").ok().flatten().unwrap_or_else(|| "screening failed".to_string()),
+        }),
+        _ => {
+            let (total, events) = load_events(pool, screening_id, query).await.unwrap_or_default();
+            Some(Screening::Complete {
+                total_pairs: row.try_get::<i64, _>("total_pairs").unwrap_or(0) as usize,
+                pairs_after_hoots: row.try_get::<i64, _>("pairs_after_hoots").unwrap_or(0) as usize,
+                hoots_rejections: row_hoots_rejections(&row),
+                events_found: row.try_get::<i32, _>("events_found").unwrap_or(0) as usize,
+                elapsed_ms: row.try_get::<i64, _>("elapsed_ms").unwrap_or(0) as u64,
+                params: row_params(&row),
+                fetch_warnings: row_fetch_warnings(&row),
+                total,
+                events,
             })
         }
-        None => serde_json::json!({ "status": "idle" }),
+    }
+}
+
+/// `EventQuery`'s filters; `$1` is the screening id.
+const EVENT_FILTER: &str = "screening_id = $1
+    AND ($2::DOUBLE PRECISION IS NULL OR collision_probability >= $2)
+    AND ($3::DOUBLE PRECISION IS NULL OR miss_distance_km <= $3)
+    AND ($4::TEXT IS NULL OR strpos(lower(sat_a), lower($4)) > 0 OR strpos(lower(sat_b), lower($4)) > 0)
+    AND ($5::DOUBLE PRECISION IS NULL OR tca_unix_ms >= $5)
+    AND ($6::DOUBLE PRECISION IS NULL OR tca_unix_ms < $6)
+    AND ($7::INTEGER IS NULL OR sat_a_norad_id = $7 OR sat_b_norad_id = $7)";
+
+/// One page of a screening's events under `query`'s filters and order,
+/// with the filtered total. No `limit` returns every match.
+pub(crate) async fn load_events(pool: &PgPool, screening_id: i64, query: &EventQuery) -> Result<(i64, Vec<ConjunctionEventOut>), sqlx::Error> {
+    let offset = query.offset();
+    let rows = sqlx::query(&format!(
+        "SELECT id, sat_a, sat_b, tca_unix_ms, miss_distance_km, rel_velocity_km_s, collision_probability,
+                miss_radial_km, miss_in_track_km, miss_cross_track_km, approach_angle_deg,
+                sat_a_norad_id, sat_b_norad_id, sat_a_designator, sat_b_designator, sat_a_tle_age_days, sat_b_tle_age_days,
+                COUNT(*) OVER () AS total
+         FROM conjunction_events
+         WHERE {EVENT_FILTER}
+         ORDER BY {}, id
+         LIMIT $8 OFFSET $9",
+        query.order_by().unwrap_or("tca_unix_ms"),
+    ))
+    .bind(screening_id)
+    .bind(query.min_pc)
+    .bind(query.max_miss_km)
+    .bind(query.sat.as_deref())
+    .bind(query.tca_from_unix_ms)
+    .bind(query.tca_to_unix_ms)
+    .bind(query.norad_id.map(|id| id as i32))
+    .bind(query.limit())
+    .bind(offset)
+    .fetch_all(pool)
+    .await?;
+    let total = match rows.first() {
+        Some(r) => r.try_get::<i64, _>("total")?,
+        // Past the last page the window count has no row to ride on.
+        None if offset > 0 => {
+            sqlx::query_scalar(&format!("SELECT COUNT(*) FROM conjunction_events WHERE {EVENT_FILTER}"))
+                .bind(screening_id)
+                .bind(query.min_pc)
+                .bind(query.max_miss_km)
+                .bind(query.sat.as_deref())
+                .bind(query.tca_from_unix_ms)
+                .bind(query.tca_to_unix_ms)
+                .bind(query.norad_id.map(|id| id as i32))
+                .fetch_one(pool)
+                .await?
+        }
+        None => 0,
+    };
+    Ok((total, rows.iter().map(event_from_row).collect()))
+}
+
+/// The newest public screening — running, complete or failed — as every
+/// replica sees it: the page's own default screen or a scheduled one, in
+/// any mode. `custom` starts (someone's authenticated one-off target) don't
+/// replace it; they're read through `/api/conjunction/screenings/:id/…`.
+/// `Idle` only before the first public screening.
+pub async fn get_screening(
+    state: axum::extract::State<ConjunctionAppState>,
+    axum::extract::Query(query): axum::extract::Query<EventQuery>,
+) -> Result<axum::Json<Value>, axum::http::StatusCode> {
+    if query.order_by().is_none() {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+    let query = query.paged(DEFAULT_EVENTS_PAGE);
+    let pool = &state.0.pool;
+    expire_stale_screenings(pool).await;
+    // Latest of any public target: a scheduled grid screening of the full
+    // catalog supersedes the curated-groups one on the page and vice versa.
+    let latest: Option<i64> =
+        sqlx::query_scalar("SELECT id FROM conjunction_screenings WHERE triggered_by <> 'custom' ORDER BY id DESC LIMIT 1")
+            .fetch_optional(pool)
+            .await
+            .ok()
+            .flatten();
+
+    let current = match latest {
+        Some(id) => load_screening(pool, id, &query).await.unwrap_or(Screening::Idle),
+        None => Screening::Idle,
+    };
+    let mut body = serde_json::to_value(current).unwrap_or(serde_json::json!({"status":"idle"}));
+    if body.get("events").is_some() {
+        body["limit"] = query.limit().into();
+        body["offset"] = query.offset().into();
+    }
+    Ok(axum::Json(body))
+}
+
+/// 202 once this request holds the target's lock and chunk enqueueing is
+/// underway; 409 if the same target is already being screened anywhere;
+/// 400 with the reason for a body that doesn't parse or falls outside the
+/// limits in `conjunction_params.rs`. An empty body (or `{}`) runs the
+/// defaults and is open to anyone — it's what the page's button sends.
+/// Anything else can cost hours of worker time and a hundred CelesTrak
+/// fetches, so it takes Basic-Auth with the Lighthouse token, like
+/// `/screen`: 401 without it, 403 when no token is configured. It's
+/// recorded as `custom` and never takes over the page (`get_screening`).
+pub async fn start_screening(
+    state: axum::extract::State<ConjunctionAppState>,
+    headers: axum::http::HeaderMap,
+    body: axum::body::Bytes,
+) -> axum::response::Response {
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+
+    let request: StartRequest = if body.is_empty() {
+        StartRequest::default()
+    } else {
+        match serde_json::from_slice(&body) {
+            Ok(request) => request,
+            Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        }
+    };
+    let trigger = if request.is_default() { "manual" } else { "custom" };
+    if !request.is_default() {
+        if let Err(rejection) = require_lighthouse_auth(&headers, "conjunction") {
+            return rejection.into_response();
+        }
+    }
+    let now_ms = SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis() as f64;
+    let (mode, params) = match request.resolve(now_ms) {
+        Ok(resolved) => resolved,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+
+    match launch_screening(&state.0.pool, mode, params, trigger).await {
+        Ok(Some(_)) => StatusCode::ACCEPTED.into_response(),
+        Ok(None) => StatusCode::CONFLICT.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// Synthetic element sets with known encounters, for this module's tests
+/// and the ones built on it.
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+
+    /// 2024-01-01T00:00:00Z, the epoch of every synthetic element set.
+    pub(crate) const EPOCH_UNIX_MS: f64 = 1_704_067_200_000.0;
+
+    /// Drag-free, near-circular LEO element set (15.5 rev/day, ~370 km) at
+    /// `EPOCH_UNIX_MS`.
+    pub(crate) fn synthetic_sat(norad_id: u32, inclination: f64, raan: f64, arg_perigee: f64, mean_anomaly: f64) -> SatProp {
+        let line1 = format!("1 {norad_id:05}U 24001A   24001.00000000  .00000000  00000-0  00000-0 0  999");
+        let line2 = format!(
+            "2 {norad_id:05} {inclination:8.4} {raan:8.4} 0001000 {arg_perigee:8.4} {mean_anomaly:8.4} 15.50000000    1"
+        );
+        let line1 = format!("{line1}{}", tle::line_checksum(&line1));
+        let line2 = format!("{line2}{}", tle::line_checksum(&line2));
+        SatProp::new(&format!("SYN-{norad_id}"), &line1, &line2).expect("valid synthetic TLE")
+    }
+
+    /// Two polar orbits 60° apart in RAAN with identical in-plane elements
+    /// (argument of perigee 90°, mean anomaly 0°) sit over the north pole
+    /// together at epoch: a true zero-miss conjunction with TCA at epoch and
+    /// a relative speed of `2·v·sin(30°) = v` (~7.7 km/s). Same inclination
+    /// and in-plane elements mean SGP4 perturbs both identically, so the
+    /// pair re-meets over each pole every half orbit.
+    pub(crate) fn polar_crossing_pair() -> (SatProp, SatProp) {
+        (synthetic_sat(90001, 90.0, 0.0, 90.0, 0.0), synthetic_sat(90002, 90.0, 60.0, 90.0, 0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::*;
+    use super::*;
+
+    #[test]
+    fn chunk_ranges_cover_every_anchor_exactly_once() {
+        for n in [0, 1, 2, 7, 100, 613] {
+            let ranges = chunk_ranges(n, CHUNKS_PER_SCREENING);
+            let mut next = 0;
+            for &(start, end) in &ranges {
+                assert_eq!(start, next);
+                assert!(end > start);
+                next = end;
+            }
+            assert_eq!(next, n);
+        }
+    }
+
+    #[test]
+    fn chunk_ranges_balance_pairs_not_anchors() {
+        let n = 1000;
+        let ranges = chunk_ranges(n, 10);
+        let pairs = |(s, e): (usize, usize)| (s..e).map(|i| n - i - 1).sum::<usize>();
+        let total: usize = (0..n).map(|i| n - i - 1).sum();
+        // Every chunk but the trailing remainder carries at least its even
+        // share, and none carries more than one anchor's worth over it.
+        for &r in &ranges[..ranges.len() - 1] {
+            assert!(pairs(r) >= total / 10);
+            assert!(pairs(r) < total / 10 + n);
+        }
+        // Early anchors own more pairs, so early chunks span fewer anchors.
+        assert!(ranges[0].1 - ranges[0].0 < ranges[ranges.len() - 1].1 - ranges[ranges.len() - 1].0);
+    }
+
+    /// Window start that puts TCA exactly midway between two 5-minute samples,
+    /// where the pair is ~1100 km apart on both sides.
+    const MID_STEP_WINDOW_START_MS: f64 = EPOCH_UNIX_MS - 37.5 * 60_000.0;
+
+    fn window(start_unix_ms: f64) -> ScreeningParams {
+        ScreeningParams::defaults(ScreeningMode::Fine, start_unix_ms)
+    }
+
+    #[test]
+    fn fine_search_finds_tca_between_samples() {
+        let (a, b) = polar_crossing_pair();
+        let events = propagate_pair_fine(&a, &b, &window(MID_STEP_WINDOW_START_MS));
+        let first = events.first().expect("polar crossing found");
+        assert!((first.tca_unix_ms - EPOCH_UNIX_MS).abs() < 5_000.0, "TCA off by {} ms", first.tca_unix_ms - EPOCH_UNIX_MS);
+        assert!(first.miss_distance_km < 1.0, "miss {} km", first.miss_distance_km);
+        assert!((first.rel_velocity_km_s - 7.69).abs() < 0.3, "rel velocity {} km/s", first.rel_velocity_km_s);
+        // Two pole passages per ~93-minute orbit across the 24h window.
+        assert!(events.len() >= 28, "only {} crossings found", events.len());
+        assert!(events.iter().all(|e| e.miss_distance_km < 1.0));
+    }
+
+    #[test]
+    fn event_breaks_miss_vector_into_rtn_and_reports_approach_angle() {
+        let (a, b) = polar_crossing_pair();
+        let events = propagate_pair_fine(&a, &b, &window(MID_STEP_WINDOW_START_MS));
+        let first = events.first().expect("polar crossing found");
+        let rtn = [first.miss_radial_km, first.miss_in_track_km, first.miss_cross_track_km].map(Option::unwrap);
+        let norm = rtn.iter().map(|c| c * c).sum::<f32>().sqrt();
+        assert!((norm - first.miss_distance_km).abs() < 1e-3, "RTN norm {norm} vs miss {}", first.miss_distance_km);
+        // The planes differ by 60° of RAAN and meet at the pole, where the
+        // ground tracks cross at exactly that angle.
+        let angle = first.approach_angle_deg.unwrap();
+        assert!((angle - 60.0).abs() < 2.0, "approach angle {angle}°");
+    }
+
+    #[test]
+    fn coarse_search_misses_tca_between_samples() {
+        let (a, b) = polar_crossing_pair();
+        let events = propagate_pair(&a, &b, &window(MID_STEP_WINDOW_START_MS));
+        assert!(events.iter().all(|e| (e.tca_unix_ms - EPOCH_UNIX_MS).abs() > 60_000.0));
+    }
+
+    #[test]
+    fn coarse_search_refines_a_sampled_minimum_to_the_same_state_as_fine() {
+        // Window aligned so a sample lands on the crossing itself.
+        let (a, b) = polar_crossing_pair();
+        let aligned = EPOCH_UNIX_MS - 35.0 * 60_000.0;
+        let coarse = propagate_pair(&a, &b, &window(aligned));
+        let fine = propagate_pair_fine(&a, &b, &window(aligned));
+        let (c, f) = (coarse.first().expect("sampled crossing found"), fine.first().expect("crossing found"));
+        assert!((c.tca_unix_ms - f.tca_unix_ms).abs() < 2.0 * TCA_TOLERANCE_MS);
+        assert!((c.miss_distance_km - f.miss_distance_km).abs() < 1e-3);
+        assert!((c.rel_velocity_km_s - f.rel_velocity_km_s).abs() < 1e-3);
+    }
+
+    #[test]
+    fn grid_screening_reports_each_crossing_once_across_chunks() {
+        let (a, b) = polar_crossing_pair();
+        let fine = propagate_pair_fine(&a, &b, &window(MID_STEP_WINDOW_START_MS));
+        let props = vec![Some(a), None, Some(b)];
+        let mut grid = Vec::new();
+        let params = window(MID_STEP_WINDOW_START_MS);
+        for (start, end) in step_ranges(conjunction_grid::grid_steps(&params), CHUNKS_PER_SCREENING) {
+            grid.extend(conjunction_grid::screen_step_range(&props, start, end, &params, &ChunkProgress::default()).1);
+        }
+        assert!(grid.iter().all(|e| e.sat_indices == (0, 2)));
+        for f in &fine {
+            let matches = grid.iter().filter(|g| (g.tca_unix_ms - f.tca_unix_ms).abs() < 1_000.0).count();
+            assert_eq!(matches, 1, "crossing at {} found {matches} times", f.tca_unix_ms);
+        }
+        // Fine's last sample is one step short of the window's end; the
+        // grid covers it fully, so it may find at most one more.
+        assert!(grid.len() - fine.len() <= 1, "{} grid vs {} fine", grid.len(), fine.len());
+    }
+
+    #[test]
+    fn step_ranges_split_the_window_evenly() {
+        let steps = conjunction_grid::grid_steps(&window(EPOCH_UNIX_MS));
+        let ranges = step_ranges(steps, CHUNKS_PER_SCREENING);
+        assert_eq!(ranges.first().map(|r| r.0), Some(0));
+        assert_eq!(ranges.last().map(|r| r.1), Some(steps));
+        assert!(ranges.windows(2).all(|w| w[0].1 == w[1].0));
+    }
+
+    #[test]
+    fn fine_search_reports_nothing_for_pair_that_never_meets() {
+        // Same planes, but B trails half an orbit behind: whenever A is over
+        // one pole B is over the other, and the pair never closes within 120°.
+        let a = synthetic_sat(90001, 90.0, 0.0, 90.0, 0.0);
+        let b = synthetic_sat(90003, 90.0, 60.0, 90.0, 180.0);
+        assert!(propagate_pair_fine(&a, &b, &window(MID_STEP_WINDOW_START_MS)).is_empty());
+    }
+
+    #[test]
+    fn hoots_stages_drop_the_pair_that_never_meets_and_keep_the_crossing() {
+        let (a, b) = polar_crossing_pair();
+        // Crosses A's plane over the poles too, but always at the opposite one.
+        let c = synthetic_sat(90003, 90.0, 60.0, 90.0, 180.0);
+        let props = [Some(a), Some(b), Some(c)];
+        let params = window(MID_STEP_WINDOW_START_MS);
+        let (pairs, rejections, events) = screen_anchor_range(&props, &orbit_paths(&props, &params), 0, 3, &params, propagate_pair_fine, &ChunkProgress::default());
+        assert_eq!(rejections, HootsRejections { altitude: 0, orbit_path: 0, time: 1 });
+        // B and C share a plane, so only propagation can tell them apart.
+        assert_eq!(pairs, 2);
+        assert!(events.len() >= 28 && events.iter().all(|e| e.sat_indices == (0, 1)));
+    }
+
+    #[test]
+    fn hoots_stages_keep_a_real_crossing_with_no_buffer() {
+        let (a, b) = polar_crossing_pair();
+        let props = [Some(a), Some(b)];
+        let params = ScreeningParams { hoots_buffer_km: 0.0, miss_threshold_km: 0.1, ..window(MID_STEP_WINDOW_START_MS) };
+        let (pairs, rejections, events) = screen_anchor_range(&props, &orbit_paths(&props, &params), 0, 2, &params, propagate_pair_fine, &ChunkProgress::default());
+        assert_eq!((pairs, rejections), (1, HootsRejections::default()));
+        assert!(events.len() >= 28);
+    }
+
+    #[test]
+    fn public_screening_pages_its_events() {
+        let query = |json: &str| serde_json::from_str::<EventQuery>(json).unwrap().paged(DEFAULT_EVENTS_PAGE);
+        assert_eq!(query("{}").limit(), Some(DEFAULT_EVENTS_PAGE));
+        assert_eq!(query(r#"{"limit": 100000}"#).limit(), Some(DEFAULT_EVENTS_PAGE));
+        assert_eq!(query(r#"{"limit": 10, "offset": 20}"#).limit(), Some(10));
+    }
+
+    #[test]
+    fn reads_catalog_ids_from_line1() {
+        let sat = synthetic_sat(25544, 51.6, 0.0, 0.0, 0.0);
+        assert_eq!(sat.norad_id(), 25544);
+        assert_eq!(sat.international_designator(), Some("2024-001A"));
     }
 }
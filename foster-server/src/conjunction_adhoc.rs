@@ -0,0 +1,248 @@
+//! `POST /api/conjunction/screen` — screen objects the caller brings against
+//! the catalog, answered inline. The use case is a planned launch or a
+//! fresh orbit determination that isn't in CelesTrak yet: "what does this
+//! come near in the next day?" shouldn't mean a full all-vs-all screening
+//! through the chunk queue.
+//!
+//! The body carries up to `MAX_OBJECTS` objects as TLE text (two- or
+//! three-line sets, concatenated) and/or OMM JSON records (CelesTrak's
+//! `FORMAT=json` shape, which `sgp4::Elements` deserializes directly), plus
+//! the same window/threshold fields as `POST /api/conjunction/start` under
+//! `params` (see `StartRequest::resolve_window`). Each object is screened
+//! one-vs-many against the `active` group from the shared TLE catalog
+//! (`tle.rs`) — no CelesTrak round trip on the request path once cached —
+//! through the same Hoots stages (`conjunction_hoots.rs`) and
+//! `propagate_pair`/`propagate_pair_fine` as a stored screening, parallel
+//! over the catalog. That is ~16k pairs per object rather than ~16k²/2, so
+//! it finishes in seconds; nothing is written to Postgres, the events come
+//! back in the response.
+//!
+//! Basic-Auth with the Lighthouse token, like the other write-side routes:
+//! it's CPU the public shouldn't be able to spend at will.
+
+use crate::conjunction::{propagate_pair, propagate_pair_fine, ConjunctionAppState, ConjunctionEventOut, SatProp, ScreeningMode};
+use crate::conjunction_hoots::{hoots_filter, HootsRejections, OrbitPath};
+use crate::conjunction_params::{ScreeningParams, StartRequest};
+use crate::lighthouse_auth::require_lighthouse_auth;
+use crate::tle::{self, TleSource};
+use axum::extract::State;
+use axum::http::{header::HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use rayon::prelude::*;
+use serde::Deserialize;
+use serde_json::json;
+use sgp4::Elements;
+use std::time::{Instant, SystemTime};
+
+const MAX_BODY_SIZE: usize = 256 * 1024;
+const MAX_OBJECTS: usize = 10;
+const CATALOG_GROUP: &str = "active";
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AdHocScreenRequest {
+    tles: Option<String>,
+    omm: Option<Vec<serde_json::Value>>,
+    #[serde(default)]
+    params: StartRequest,
+}
+
+/// Two- or three-line sets, concatenated. A two-line set is named after
+/// its catalog number.
+fn parse_tle_text(text: &str) -> Result<Vec<SatProp>, String> {
+    let lines: Vec<&str> = text.lines().map(str::trim_end).filter(|l| !l.trim().is_empty()).collect();
+    let mut sats = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let (name, line1, line2) = match (lines[i].starts_with("1 "), lines.get(i + 1), lines.get(i + 2)) {
+            (true, Some(line2), _) => {
+                i += 2;
+                (None, lines[i - 2], *line2)
+            }
+            (false, Some(line1), Some(line2)) => {
+                i += 3;
+                (Some(lines[i - 3].trim().trim_start_matches("0 ")), *line1, *line2)
+            }
+            _ => return Err(format!("incomplete TLE at line {}", i + 1)),
+        };
+        let name = match name {
+            Some(name) => name.to_string(),
+            None => format!("NORAD {}", line1.get(2..7).unwrap_or("?").trim()),
+        };
+        let sat = SatProp::new(&name, line1, line2).ok_or_else(|| format!("TLE for {name:?} does not parse"))?;
+        sats.push(sat);
+    }
+    Ok(sats)
+}
+
+fn parse_objects(request: &AdHocScreenRequest) -> Result<Vec<SatProp>, String> {
+    let mut objects = match &request.tles {
+        Some(text) => parse_tle_text(text)?,
+        None => Vec::new(),
+    };
+    for (i, record) in request.omm.iter().flatten().enumerate() {
+        let elements: Elements = serde_json::from_value(record.clone()).map_err(|e| format!("omm[{i}]: {e}"))?;
+        objects.push(SatProp::from_omm(&elements).ok_or_else(|| format!("omm[{i}]: elements do not propagate"))?);
+    }
+    if objects.is_empty() {
+        return Err("nothing to screen: give tles or omm".to_string());
+    }
+    if objects.len() > MAX_OBJECTS {
+        return Err(format!("at most {MAX_OBJECTS} objects"));
+    }
+    Ok(objects)
+}
+
+/// Each object against every catalog entry (its own catalog entry
+/// excluded, if it has one). Returns the pair count that survived the
+/// Hoots stages and what each stage rejected alongside the events,
+/// soonest first.
+fn screen_against_catalog(
+    objects: &[SatProp],
+    catalog: &[SatProp],
+    params: &ScreeningParams,
+    mode: ScreeningMode,
+) -> (usize, HootsRejections, Vec<ConjunctionEventOut>) {
+    let scan_pair = match mode {
+        ScreeningMode::Coarse => propagate_pair,
+        _ => propagate_pair_fine,
+    };
+    let path = |sat: &SatProp| OrbitPath::over_window(sat, params.start_unix_ms, params.end_unix_ms());
+    let object_paths: Vec<Option<OrbitPath>> = objects.par_iter().map(path).collect();
+    let catalog_paths: Vec<Option<OrbitPath>> = catalog.par_iter().map(path).collect();
+    let per_pair: Vec<Result<Vec<ConjunctionEventOut>, _>> = objects
+        .par_iter()
+        .zip(&object_paths)
+        .flat_map(|object| catalog.par_iter().zip(&catalog_paths).map(move |other| (object, other)))
+        .filter(|((object, _), (other, _))| object.norad_id() != other.norad_id())
+        .map(|((object, object_path), (other, other_path))| {
+            match hoots_filter(object, object_path.as_ref(), other, other_path.as_ref(), params) {
+                Some(stage) => Err(stage),
+                None => Ok(scan_pair(object, other, params)),
+            }
+        })
+        .collect();
+
+    let mut rejections = HootsRejections::default();
+    let mut pairs = 0;
+    let mut events = Vec::new();
+    for pair in per_pair {
+        match pair {
+            Ok(found) => {
+                pairs += 1;
+                events.extend(found);
+            }
+            Err(stage) => rejections.count(stage),
+        }
+    }
+    events.sort_by(|a, b| a.tca_unix_ms.total_cmp(&b.tca_unix_ms));
+    (pairs, rejections, events)
+}
+
+pub async fn screen_objects(State(state): State<ConjunctionAppState>, headers: HeaderMap, body: axum::body::Bytes) -> Response {
+    if let Err(rejection) = require_lighthouse_auth(&headers, "conjunction") {
+        return rejection.into_response();
+    }
+
+    if body.len() > MAX_BODY_SIZE {
+        return (StatusCode::PAYLOAD_TOO_LARGE, "Exceeds 256KB limit").into_response();
+    }
+
+    let request: AdHocScreenRequest = match serde_json::from_slice(&body) {
+        Ok(r) => r,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    let objects = match parse_objects(&request) {
+        Ok(objects) => objects,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let now_ms = SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis() as f64;
+    let (mode, params) = match request.params.resolve_window(&[CATALOG_GROUP], now_ms) {
+        Ok(resolved) => resolved,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+
+    let catalog = match tle::get_catalog(Some(&state.pool), &TleSource::group(CATALOG_GROUP)).await {
+        Ok(catalog) => catalog,
+        Err(e) => return (StatusCode::SERVICE_UNAVAILABLE, e.to_string()).into_response(),
+    };
+    let fetched_at = catalog.fetched_at;
+
+    let started = Instant::now();
+    let screened = tokio::task::spawn_blocking(move || {
+        let catalog: Vec<SatProp> = catalog
+            .tles
+            .par_iter()
+            .filter_map(|(name, l1, l2)| {
+                if name.is_empty() {
+                    SatProp::new(&format!("NORAD {}", l1.get(2..7)?.trim()), l1, l2)
+                } else {
+                    SatProp::new(name, l1, l2)
+                }
+            })
+            .collect();
+        let (pairs, rejections, events) = screen_against_catalog(&objects, &catalog, &params, mode);
+        let objects: Vec<_> = objects
+            .iter()
+            .map(|o| json!({ "name": o.name, "norad_id": o.norad_id(), "international_designator": o.international_designator() }))
+            .collect();
+        json!({
+            "mode": mode.as_str(),
+            "params": params,
+            "objects": objects,
+            "catalog_group": CATALOG_GROUP,
+            "catalog_size": catalog.len(),
+            "catalog_fetched_at": fetched_at.to_rfc3339(),
+            "pairs_after_hoots": pairs,
+            "hoots_rejections": rejections,
+            "events": events,
+        })
+    })
+    .await;
+
+    match screened {
+        Ok(mut result) => {
+            result["elapsed_ms"] = json!(started.elapsed().as_millis() as u64);
+            axum::Json(result).into_response()
+        }
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISS_LINE1: &str = "1 25544U 98067A   24001.50000000  .00016717  00000-0  10270-3 0  9009";
+    const ISS_LINE2: &str = "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537";
+
+    #[test]
+    fn reads_two_and_three_line_sets_together() {
+        let text = format!("ISS (ZARYA)\n{ISS_LINE1}\n{ISS_LINE2}\n\n{ISS_LINE1}\n{ISS_LINE2}\n");
+        let sats = parse_tle_text(&text).unwrap();
+        assert_eq!(sats.len(), 2);
+        assert_eq!(sats[0].name, "ISS (ZARYA)");
+        assert_eq!(sats[1].name, "NORAD 25544");
+        assert_eq!(sats[1].international_designator(), Some("1998-067A"));
+        assert!(parse_tle_text(ISS_LINE1).is_err());
+    }
+
+    #[test]
+    fn reads_omm_records() {
+        let request: AdHocScreenRequest = serde_json::from_value(json!({
+            "omm": [{
+                "OBJECT_NAME": "ISS (ZARYA)", "OBJECT_ID": "1998-067A", "EPOCH": "2024-01-01T12:00:00.000000",
+                "MEAN_MOTION": 15.72125391, "ECCENTRICITY": 0.0006703, "INCLINATION": 51.6416,
+                "RA_OF_ASC_NODE": 247.4627, "ARG_OF_PERICENTER": 130.536, "MEAN_ANOMALY": 325.0288,
+                "EPHEMERIS_TYPE": 0, "CLASSIFICATION_TYPE": "U", "NORAD_CAT_ID": 25544, "ELEMENT_SET_NO": 999,
+                "REV_AT_EPOCH": 56353, "BSTAR": 0.0001027, "MEAN_MOTION_DOT": 0.00016717, "MEAN_MOTION_DDOT": 0
+            }],
+            "params": { "duration_hours": 6 }
+        }))
+        .unwrap();
+        let objects = parse_objects(&request).unwrap();
+        assert_eq!(objects[0].name, "ISS (ZARYA)");
+        assert_eq!(objects[0].norad_id(), 25544);
+        assert_eq!(objects[0].international_designator(), Some("1998-067A"));
+    }
+}
@@ -0,0 +1,515 @@
+//! CCSDS Conjunction Data Message (CDM, CCSDS 508.0-B-1) export for stored
+//! events — the format operators and Space-Track exchange, so a screening
+//! result can be handed to other tools instead of only read off the page.
+//!
+//! Nothing here re-runs the screen. The event's two objects are looked up
+//! by their indices into the screening's `tle_snapshot` and re-propagated
+//! with the same `SatProp` the screen used, to the stored TCA, so the state
+//! vectors, relative geometry and miss distance in the message all come
+//! from one consistent SGP4 evaluation.
+//!
+//! SGP4 works in TEME, which CDM 1.0 doesn't allow as a `REF_FRAME`; state
+//! vectors are rotated to ITRF by GMST (IAU-82), neglecting polar motion
+//! (metres at LEO). Relative position and velocity are Object2 minus
+//! Object1 in Object1's RTN frame, taken from the inertial states. TLEs
+//! carry no covariance, so each object's is the per-class default from
+//! conjunction_pc.rs on the position diagonal, with velocity terms zeroed
+//! and `COVARIANCE_METHOD = DEFAULT` saying so.
+//!
+//! KVN by default; XML with `?format=xml` or an `Accept` whose most
+//! preferred media range is `application/xml` or `text/xml` — a browser's
+//! link-click `Accept` lists XML too, but below HTML, and gets KVN.
+
+use crate::conjunction::{row_params, rtn_components, ConjunctionAppState, SatProp, J2000_UNIX_MS};
+use crate::conjunction_params::ScreeningParams;
+use crate::conjunction_pc::{class_defaults, ObjectClass};
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use serde_json::Value;
+use sqlx::Row;
+
+const EARTH_ROTATION_RAD_S: f64 = 7.292_115_146_706_979e-5;
+const ORIGINATOR: &str = "JAYDANHOWARD";
+
+struct Field {
+    key: &'static str,
+    value: String,
+    unit: Option<&'static str>,
+}
+
+fn field(key: &'static str, value: impl Into<String>) -> Field {
+    Field { key, value: value.into(), unit: None }
+}
+
+fn measured(key: &'static str, value: f64, decimals: usize, unit: &'static str) -> Field {
+    Field { key, value: format!("{value:.decimals$}"), unit: Some(unit) }
+}
+
+fn comment(text: impl Into<String>) -> Field {
+    field("COMMENT", text)
+}
+
+struct CdmObject {
+    metadata: Vec<Field>,
+    comments: Vec<Field>,
+    state_vector: Vec<Field>,
+    covariance: Vec<Field>,
+}
+
+struct Cdm {
+    header: Vec<Field>,
+    relative: Vec<Field>,
+    relative_state: Vec<Field>,
+    screening: Vec<Field>,
+    objects: [CdmObject; 2],
+}
+
+fn format_time(unix_ms: f64) -> String {
+    chrono::DateTime::from_timestamp_millis(unix_ms.round() as i64)
+        .map(|t| t.format("%Y-%m-%dT%H:%M:%S%.3f").to_string())
+        .unwrap_or_default()
+}
+
+/// TEME → ITRF (pseudo-Earth-fixed) by the GMST rotation; the velocity also
+/// loses the frame's own rotation, `ω × r`.
+pub(crate) fn teme_to_itrf(r: &[f64; 3], v: &[f64; 3], unix_ms: f64) -> ([f64; 3], [f64; 3]) {
+    let gmst = sgp4::iau_epoch_to_sidereal_time((unix_ms - J2000_UNIX_MS) / (365.25 * 86_400_000.0));
+    let (s, c) = gmst.sin_cos();
+    let rotate = |a: &[f64; 3]| [c * a[0] + s * a[1], -s * a[0] + c * a[1], a[2]];
+    let r_itrf = rotate(r);
+    let v_rot = rotate(v);
+    let v_itrf = [
+        v_rot[0] + EARTH_ROTATION_RAD_S * r_itrf[1],
+        v_rot[1] - EARTH_ROTATION_RAD_S * r_itrf[0],
+        v_rot[2],
+    ];
+    (r_itrf, v_itrf)
+}
+
+fn object_type(class: ObjectClass) -> &'static str {
+    match class {
+        ObjectClass::Payload => "PAYLOAD",
+        ObjectClass::RocketBody => "ROCKET BODY",
+        ObjectClass::Debris => "DEBRIS",
+    }
+}
+
+fn cdm_object(label: &'static str, sat: &SatProp, r: &[f64; 3], v: &[f64; 3], tca_unix_ms: f64) -> CdmObject {
+    let class = ObjectClass::from_name(&sat.name);
+    let (r_itrf, v_itrf) = teme_to_itrf(r, v, tca_unix_ms);
+    let d = class_defaults(class);
+    let var = |sigma_km: f64| (sigma_km * 1000.0).powi(2);
+    let covariance_terms: [(&'static str, f64); 21] = [
+        ("CR_R", var(d.sigma_radial_km)),
+        ("CT_R", 0.0),
+        ("CT_T", var(d.sigma_in_track_km)),
+        ("CN_R", 0.0),
+        ("CN_T", 0.0),
+        ("CN_N", var(d.sigma_cross_track_km)),
+        ("CRDOT_R", 0.0),
+        ("CRDOT_T", 0.0),
+        ("CRDOT_N", 0.0),
+        ("CRDOT_RDOT", 0.0),
+        ("CTDOT_R", 0.0),
+        ("CTDOT_T", 0.0),
+        ("CTDOT_N", 0.0),
+        ("CTDOT_RDOT", 0.0),
+        ("CTDOT_TDOT", 0.0),
+        ("CNDOT_R", 0.0),
+        ("CNDOT_T", 0.0),
+        ("CNDOT_N", 0.0),
+        ("CNDOT_RDOT", 0.0),
+        ("CNDOT_TDOT", 0.0),
+        ("CNDOT_NDOT", 0.0),
+    ];
+    let age_days = (tca_unix_ms - sat.epoch_unix_ms()) / 86_400_000.0;
+    CdmObject {
+        metadata: vec![
+            field("OBJECT", label),
+            field("OBJECT_DESIGNATOR", sat.norad_id().to_string()),
+            field("CATALOG_NAME", "SATCAT"),
+            field("OBJECT_NAME", sat.name.clone()),
+            field("INTERNATIONAL_DESIGNATOR", sat.international_designator().unwrap_or("UNKNOWN")),
+            field("OBJECT_TYPE", object_type(class)),
+            field("EPHEMERIS_NAME", "NONE"),
+            field("COVARIANCE_METHOD", "DEFAULT"),
+            field("MANEUVERABLE", "N/A"),
+            field("ORBIT_CENTER", "EARTH"),
+            field("REF_FRAME", "ITRF"),
+        ],
+        comments: vec![
+            comment(format!("SGP4 from TLE epoch {}, {age_days:.2} days before TCA", format_time(sat.epoch_unix_ms()))),
+            comment("Position covariance is a per-class default; velocity terms not modelled"),
+        ],
+        state_vector: vec![
+            measured("X", r_itrf[0], 6, "km"),
+            measured("Y", r_itrf[1], 6, "km"),
+            measured("Z", r_itrf[2], 6, "km"),
+            measured("X_DOT", v_itrf[0], 9, "km/s"),
+            measured("Y_DOT", v_itrf[1], 9, "km/s"),
+            measured("Z_DOT", v_itrf[2], 9, "km/s"),
+        ],
+        covariance: covariance_terms
+            .into_iter()
+            .map(|(key, value)| {
+                let unit = match key.matches("DOT").count() {
+                    0 => "m**2",
+                    1 => "m**2/s",
+                    _ => "m**2/s**2",
+                };
+                measured(key, value, 4, unit)
+            })
+            .collect(),
+    }
+}
+
+pub(crate) struct StoredEvent {
+    pub(crate) id: i64,
+    screening_id: i64,
+    pub(crate) tca_unix_ms: f64,
+    pub(crate) collision_probability: Option<f64>,
+    params: Option<ScreeningParams>,
+    pub(crate) sat_a: SatProp,
+    pub(crate) sat_b: SatProp,
+}
+
+fn build_cdm(event: &StoredEvent, creation_unix_ms: f64) -> Option<Cdm> {
+    let tca = event.tca_unix_ms;
+    let (ra, va) = event.sat_a.eci_state(tca)?;
+    let (rb, vb) = event.sat_b.eci_state(tca)?;
+    let dr = [rb[0] - ra[0], rb[1] - ra[1], rb[2] - ra[2]];
+    let dv = [vb[0] - va[0], vb[1] - va[1], vb[2] - va[2]];
+    let pos_rtn = rtn_components(&ra, &va, &dr);
+    let vel_rtn = rtn_components(&ra, &va, &dv);
+    let norm = |a: &[f64; 3]| (a[0] * a[0] + a[1] * a[1] + a[2] * a[2]).sqrt();
+
+    let mut screening = Vec::new();
+    if let Some(params) = &event.params {
+        let threshold_m = params.miss_threshold_km * 1000.0;
+        screening.extend([
+            field("START_SCREEN_PERIOD", format_time(params.start_unix_ms)),
+            field("STOP_SCREEN_PERIOD", format_time(params.end_unix_ms())),
+            field("SCREEN_VOLUME_FRAME", "RTN"),
+            field("SCREEN_VOLUME_SHAPE", "ELLIPSOID"),
+            measured("SCREEN_VOLUME_X", threshold_m, 1, "m"),
+            measured("SCREEN_VOLUME_Y", threshold_m, 1, "m"),
+            measured("SCREEN_VOLUME_Z", threshold_m, 1, "m"),
+        ]);
+    }
+    if let Some(pc) = event.collision_probability {
+        screening.push(field("COLLISION_PROBABILITY", format!("{pc:.6e}")));
+        screening.push(field("COLLISION_PROBABILITY_METHOD", "FOSTER-1992"));
+    }
+
+    Some(Cdm {
+        header: vec![
+            field("CREATION_DATE", format_time(creation_unix_ms)),
+            field("ORIGINATOR", ORIGINATOR),
+            field("MESSAGE_ID", format!("{ORIGINATOR}-{}-{}", event.screening_id, event.id)),
+        ],
+        relative: vec![
+            field("TCA", format_time(tca)),
+            measured("MISS_DISTANCE", norm(&dr) * 1000.0, 3, "m"),
+            measured("RELATIVE_SPEED", norm(&dv) * 1000.0, 3, "m/s"),
+        ],
+        relative_state: vec![
+            measured("RELATIVE_POSITION_R", pos_rtn[0] * 1000.0, 3, "m"),
+            measured("RELATIVE_POSITION_T", pos_rtn[1] * 1000.0, 3, "m"),
+            measured("RELATIVE_POSITION_N", pos_rtn[2] * 1000.0, 3, "m"),
+            measured("RELATIVE_VELOCITY_R", vel_rtn[0] * 1000.0, 3, "m/s"),
+            measured("RELATIVE_VELOCITY_T", vel_rtn[1] * 1000.0, 3, "m/s"),
+            measured("RELATIVE_VELOCITY_N", vel_rtn[2] * 1000.0, 3, "m/s"),
+        ],
+        screening,
+        objects: [
+            cdm_object("OBJECT1", &event.sat_a, &ra, &va, tca),
+            cdm_object("OBJECT2", &event.sat_b, &rb, &vb, tca),
+        ],
+    })
+}
+
+impl Cdm {
+    fn to_kvn(&self) -> String {
+        let mut out = String::from("CCSDS_CDM_VERS                   = 1.0\n");
+        let mut write = |fields: &[Field]| {
+            for f in fields {
+                if f.key == "COMMENT" {
+                    out.push_str(&format!("COMMENT {}\n", f.value));
+                } else {
+                    let unit = f.unit.map(|u| format!(" [{u}]")).unwrap_or_default();
+                    out.push_str(&format!("{:<32} = {}{unit}\n", f.key, f.value));
+                }
+            }
+        };
+        write(&self.header);
+        write(&self.relative);
+        write(&self.relative_state);
+        write(&self.screening);
+        for object in &self.objects {
+            write(&object.metadata);
+            write(&object.comments);
+            write(&object.state_vector);
+            write(&object.covariance);
+        }
+        out
+    }
+
+    fn to_xml(&self) -> String {
+        fn escape(s: &str) -> String {
+            s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+        }
+        fn write(out: &mut String, indent: usize, fields: &[Field]) {
+            for f in fields {
+                let unit = f.unit.map(|u| format!(" units=\"{u}\"")).unwrap_or_default();
+                out.push_str(&format!("{:indent$}<{key}{unit}>{}</{key}>\n", "", escape(&f.value), key = f.key));
+            }
+        }
+        let mut out = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <cdm xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+             xsi:noNamespaceSchemaLocation=\"http://sanaregistry.org/r/ndmxml/ndmxml-1.0-master.xsd\" \
+             id=\"CCSDS_CDM_VERS\" version=\"1.0\">\n  <header>\n",
+        );
+        write(&mut out, 4, &self.header);
+        out.push_str("  </header>\n  <body>\n    <relativeMetadataData>\n");
+        write(&mut out, 6, &self.relative);
+        out.push_str("      <relativeStateVector>\n");
+        write(&mut out, 8, &self.relative_state);
+        out.push_str("      </relativeStateVector>\n");
+        write(&mut out, 6, &self.screening);
+        out.push_str("    </relativeMetadataData>\n");
+        for object in &self.objects {
+            out.push_str("    <segment>\n      <metadata>\n");
+            write(&mut out, 8, &object.metadata);
+            out.push_str("      </metadata>\n      <data>\n");
+            write(&mut out, 8, &object.comments);
+            out.push_str("        <stateVector>\n");
+            write(&mut out, 10, &object.state_vector);
+            out.push_str("        </stateVector>\n        <covarianceMatrix>\n");
+            write(&mut out, 10, &object.covariance);
+            out.push_str("        </covarianceMatrix>\n      </data>\n    </segment>\n");
+        }
+        out.push_str("  </body>\n</cdm>\n");
+        out
+    }
+}
+
+/// A stored event with both objects rebuilt from the screening's
+/// `tle_snapshot`: 404 for an unknown event, 422 if they can't be.
+pub(crate) async fn load_event(pool: &sqlx::PgPool, event_id: i64) -> Result<StoredEvent, StatusCode> {
+    let row = sqlx::query(
+        "SELECT e.id, e.screening_id, e.tca_unix_ms, e.collision_probability, e.sat_a_index, e.sat_b_index,
+                s.tle_snapshot, s.params, s.window_start_unix_ms, s.mode
+         FROM conjunction_events e
+         JOIN conjunction_screenings s ON s.id = e.screening_id
+         WHERE e.id = $1",
+    )
+    .bind(event_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Conjunction event lookup failed: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    // Events from before snapshot indices were recorded can't be traced
+    // back to the element sets that produced them.
+    let (Some(a), Some(b)) = (
+        row.try_get::<Option<i32>, _>("sat_a_index").ok().flatten(),
+        row.try_get::<Option<i32>, _>("sat_b_index").ok().flatten(),
+    ) else {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    };
+    let tles: Vec<(String, String, String)> = row
+        .try_get::<Option<Value>, _>("tle_snapshot")
+        .ok()
+        .flatten()
+        .and_then(|v| serde_json::from_value(v).ok())
+        .ok_or(StatusCode::UNPROCESSABLE_ENTITY)?;
+    let prop = |i: i32| {
+        let (name, line1, line2) = tles.get(usize::try_from(i).ok()?)?;
+        SatProp::new(name, line1, line2)
+    };
+    Ok(StoredEvent {
+        id: row.try_get("id").unwrap_or(event_id),
+        screening_id: row.try_get("screening_id").unwrap_or_default(),
+        tca_unix_ms: row.try_get("tca_unix_ms").unwrap_or_default(),
+        collision_probability: row.try_get("collision_probability").ok().flatten(),
+        params: row_params(&row),
+        sat_a: prop(a).ok_or(StatusCode::UNPROCESSABLE_ENTITY)?,
+        sat_b: prop(b).ok_or(StatusCode::UNPROCESSABLE_ENTITY)?,
+    })
+}
+
+/// Whether an `Accept` header asks for XML: an exact `application/xml` or
+/// `text/xml` among its most preferred media ranges. A browser following a
+/// link also lists XML, but below `text/html`, so it gets KVN.
+fn prefers_xml(accept: &str) -> bool {
+    let ranges: Vec<(&str, f32)> = accept
+        .split(',')
+        .map(|range| {
+            let mut parts = range.split(';').map(str::trim);
+            let media = parts.next().unwrap_or("");
+            let q = parts
+                .find_map(|p| p.strip_prefix("q="))
+                .and_then(|q| q.parse().ok())
+                .unwrap_or(1.0);
+            (media, q)
+        })
+        .collect();
+    let best = ranges.iter().map(|&(_, q)| q).fold(0.0, f32::max);
+    best > 0.0
+        && ranges
+            .iter()
+            .any(|&(media, q)| q == best && (media.eq_ignore_ascii_case("application/xml") || media.eq_ignore_ascii_case("text/xml")))
+}
+
+#[derive(Deserialize)]
+pub struct CdmQuery {
+    format: Option<String>,
+}
+
+/// `GET /api/conjunction/events/{id}/cdm` — 404 for an unknown event, 422
+/// for one whose objects can't be re-propagated.
+pub async fn get_event_cdm(
+    State(state): State<ConjunctionAppState>,
+    Path(event_id): Path<i64>,
+    Query(query): Query<CdmQuery>,
+    headers: HeaderMap,
+) -> Response {
+    let xml = match query.format.as_deref() {
+        Some("xml") => true,
+        Some("kvn") => false,
+        Some(_) => return StatusCode::BAD_REQUEST.into_response(),
+        None => headers.get(header::ACCEPT).and_then(|v| v.to_str().ok()).is_some_and(prefers_xml),
+    };
+    let event = match load_event(&state.pool, event_id).await {
+        Ok(event) => event,
+        Err(status) => return status.into_response(),
+    };
+    let now_ms = chrono::Utc::now().timestamp_millis() as f64;
+    let Some(cdm) = build_cdm(&event, now_ms) else {
+        return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    };
+    let filename = format!("cdm-{}.{}", event.id, if xml { "xml" } else { "txt" });
+    let disposition = format!("inline; filename=\"{filename}\"");
+    if xml {
+        ([(header::CONTENT_TYPE, "application/xml".to_string()), (header::CONTENT_DISPOSITION, disposition)], cdm.to_xml())
+            .into_response()
+    } else {
+        ([(header::CONTENT_TYPE, "text/plain; charset=utf-8".to_string()), (header::CONTENT_DISPOSITION, disposition)], cdm.to_kvn())
+            .into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn browser_accept_gets_kvn_and_explicit_xml_gets_xml() {
+        let browser = "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,*/*;q=0.8";
+        assert!(!prefers_xml(browser));
+        assert!(!prefers_xml("*/*"));
+        assert!(!prefers_xml("application/xml;q=0"));
+        assert!(prefers_xml("application/xml"));
+        assert!(prefers_xml("text/plain;q=0.5, text/xml"));
+    }
+
+    #[test]
+    fn itrf_rotation_keeps_radius_and_removes_earth_rotation() {
+        // A point fixed on the rotating equator moves at ω·r inertially and
+        // not at all in ITRF.
+        let t = 1_704_067_200_000.0;
+        let gmst = sgp4::iau_epoch_to_sidereal_time((t - J2000_UNIX_MS) / (365.25 * 86_400_000.0));
+        let radius = 42_164.0;
+        let r = [radius * gmst.cos(), radius * gmst.sin(), 0.0];
+        let speed = EARTH_ROTATION_RAD_S * radius;
+        let v = [-speed * gmst.sin(), speed * gmst.cos(), 0.0];
+        let (r_itrf, v_itrf) = teme_to_itrf(&r, &v, t);
+        assert!((r_itrf[0] - radius).abs() < 1e-6 && r_itrf[1].abs() < 1e-6, "{r_itrf:?}");
+        assert!(v_itrf.iter().all(|c| c.abs() < 1e-9), "{v_itrf:?}");
+    }
+
+    /// Opening/closing tags balance, and every leaf element's text, in
+    /// document order as `(name, text)`.
+    fn xml_leaves(xml: &str) -> Vec<(String, String)> {
+        let mut open: Vec<&str> = Vec::new();
+        let mut leaves = Vec::new();
+        let mut rest = xml.strip_prefix("<?xml version=\"1.0\" encoding=\"UTF-8\"?>").expect("XML declaration");
+        while let Some(start) = rest.find('<') {
+            let text = &rest[..start];
+            let end = rest[start..].find('>').expect("unterminated tag") + start;
+            let tag = &rest[start + 1..end];
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(open.pop(), Some(name), "mismatched </{name}>");
+                if !text.trim().is_empty() {
+                    leaves.push((name.to_string(), text.to_string()));
+                }
+            } else {
+                open.push(tag.split_whitespace().next().expect("tag name"));
+            }
+            rest = &rest[end + 1..];
+        }
+        assert!(open.is_empty() && rest.trim().is_empty(), "unclosed {open:?}");
+        leaves
+    }
+
+    #[test]
+    fn cdm_from_a_stored_event_in_kvn_and_xml() {
+        use crate::conjunction::test_support::{polar_crossing_pair, EPOCH_UNIX_MS};
+        use crate::conjunction::ScreeningMode;
+
+        let (sat_a, sat_b) = polar_crossing_pair();
+        let event = StoredEvent {
+            id: 7,
+            screening_id: 3,
+            tca_unix_ms: EPOCH_UNIX_MS,
+            collision_probability: Some(1.25e-4),
+            params: Some(ScreeningParams::defaults(ScreeningMode::Fine, EPOCH_UNIX_MS)),
+            sat_a,
+            sat_b,
+        };
+        let cdm = build_cdm(&event, EPOCH_UNIX_MS).unwrap();
+
+        let kvn = cdm.to_kvn();
+        assert!(kvn.starts_with("CCSDS_CDM_VERS                   = 1.0\n"), "{kvn}");
+        let kvn: Vec<(&str, &str)> = kvn.lines().filter_map(|line| line.split_once(" = ")).map(|(k, v)| (k.trim(), v)).collect();
+        let kvn_value = |key: &str| kvn.iter().find(|(k, _)| *k == key).map(|(_, v)| *v);
+        assert_eq!(kvn_value("TCA"), Some("2024-01-01T00:00:00.000"));
+        assert_eq!(kvn_value("MESSAGE_ID"), Some("JAYDANHOWARD-3-7"));
+        assert_eq!(kvn_value("COLLISION_PROBABILITY"), Some("1.250000e-4"));
+        let miss = kvn_value("MISS_DISTANCE").and_then(|v| v.strip_suffix(" [m]")).unwrap();
+        assert!(miss.parse::<f64>().unwrap() < 1_000.0, "{miss}");
+        let starts: Vec<usize> = kvn.iter().enumerate().filter(|(_, (k, _))| *k == "OBJECT").map(|(i, _)| i).collect();
+        assert_eq!(starts.iter().map(|&i| kvn[i].1).collect::<Vec<_>>(), ["OBJECT1", "OBJECT2"]);
+        // Each object's block: metadata, its state vector, then all 21
+        // covariance terms, the position diagonal filled in.
+        for (i, &start) in starts.iter().enumerate() {
+            let block = &kvn[start..starts.get(i + 1).copied().unwrap_or(kvn.len())];
+            let value = |key: &str| block.iter().find(|(k, _)| *k == key).map(|(_, v)| *v);
+            assert_eq!(value("OBJECT_DESIGNATOR"), Some(["90001", "90002"][i]));
+            assert!(value("X").is_some_and(|v| v.ends_with(" [km]")));
+            let covariance = &block[block.iter().position(|(k, _)| *k == "Z_DOT").unwrap() + 1..];
+            assert_eq!(covariance.len(), 21, "{covariance:?}");
+            assert_eq!((covariance[0].0, covariance[20].0), ("CR_R", "CNDOT_NDOT"));
+            assert!(value("CR_R").is_some_and(|v| v.ends_with(" [m**2]") && v != "0.0000 [m**2]"));
+        }
+
+        // The XML is well-formed and carries the same values, in the same
+        // order, as the KVN (which adds only the version line and units).
+        let xml = cdm.to_xml();
+        let leaves = xml_leaves(&xml);
+        let kvn_fields: Vec<(String, String)> = kvn
+            .iter()
+            .skip(1)
+            .map(|&(k, v)| (k.to_string(), v.split(" [").next().unwrap_or(v).to_string()))
+            .collect();
+        let xml_fields: Vec<(String, String)> = leaves.into_iter().filter(|(k, _)| k != "COMMENT").collect();
+        assert_eq!(xml_fields, kvn_fields);
+        assert!(xml.contains(&format!("<MISS_DISTANCE units=\"m\">{miss}</MISS_DISTANCE>")), "{xml}");
+    }
+}
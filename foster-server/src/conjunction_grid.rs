@@ -0,0 +1,130 @@
+//! `ScreeningMode::Grid` — screening by spatial index instead of by pair.
+//! Anchor-range screening propagates every Hoots-surviving pair over the
+//! whole window, which is quadratic in SGP4 calls and hopeless for the
+//! ~16k-object `active` catalog. Here each satellite is propagated once per
+//! `GRID_STEP_MS` sample, positions are bucketed into a uniform 3D grid,
+//! and only pairs sharing or neighbouring a cell are looked at further.
+//!
+//! Sample `k` owns the half-open interval `[t_k - h/2, t_k + h/2)`. Two
+//! objects whose range dips under the miss threshold inside it are at most
+//! `threshold + MAX_CLOSING_SPEED_KM_S · h/2` apart at `t_k`, which is the
+//! cell size, so they always land in adjacent cells. Neighbours are then
+//! culled by their straight-line closest approach within the interval (the
+//! relative acceleration of two objects that close together is tiny, so
+//! `LINEAR_MARGIN_KM` covers the curvature), and survivors get the same
+//! range-rate root solve as the other modes, accepted only if the root
+//! falls inside the owned interval — so each encounter is reported once, by
+//! one sample, even when chunks split the window between replicas.
+//!
+//! The grid keeps its own one-minute sampling whatever the screening's
+//! `step_minutes` (which only governs the pairwise modes): the cell size,
+//! and with it the neighbour count, grows with the step.
+//!
+//! Chunks of a grid screening cut the window's sample range rather than the
+//! anchor range; `conjunction_chunks.sat_start`/`sat_end` carry sample
+//! indices for them.
+
+use crate::conjunction::{dot, event_at_tca, ChunkProgress, find_range_rate_root, relative_state, ConjunctionEventOut, SatProp, MAX_CLOSING_SPEED_KM_S};
+use crate::conjunction_params::ScreeningParams;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+const GRID_STEP_MS: f64 = 60_000.0;
+const LINEAR_MARGIN_KM: f64 = 5.0;
+
+/// Samples across the window, including one on its far edge so the last
+/// half-interval is owned too.
+pub(crate) fn grid_steps(params: &ScreeningParams) -> usize {
+    ((params.end_unix_ms() - params.start_unix_ms) / GRID_STEP_MS).ceil() as usize + 1
+}
+
+/// Pairs `(i, j)`, `i < j`, in the same or a neighbouring cell whose
+/// straight-line closest approach within `±h/2` comes near the threshold.
+fn candidate_pairs(states: &[Option<([f64; 3], [f64; 3])>], threshold_km: f64) -> Vec<(usize, usize)> {
+    let cell_km = threshold_km + MAX_CLOSING_SPEED_KM_S * GRID_STEP_MS / 2_000.0;
+    let cell_of = |r: &[f64; 3]| r.map(|c| (c / cell_km).floor() as i64);
+    let mut grid: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+    for (i, state) in states.iter().enumerate() {
+        if let Some((r, _)) = state {
+            grid.entry(cell_of(r)).or_default().push(i);
+        }
+    }
+
+    let half_step_s = GRID_STEP_MS / 2_000.0;
+    let mut pairs = Vec::new();
+    for (i, state) in states.iter().enumerate() {
+        let Some((ri, vi)) = state else { continue };
+        let [cx, cy, cz] = cell_of(ri);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let Some(members) = grid.get(&[cx + dx, cy + dy, cz + dz]) else { continue };
+                    for &j in members.iter().filter(|&&j| j > i) {
+                        let Some((rj, vj)) = &states[j] else { continue };
+                        let dr = [rj[0] - ri[0], rj[1] - ri[1], rj[2] - ri[2]];
+                        let dv = [vj[0] - vi[0], vj[1] - vi[1], vj[2] - vi[2]];
+                        let speed_sq = dot(&dv, &dv);
+                        let t = if speed_sq > 0.0 { (-dot(&dr, &dv) / speed_sq).clamp(-half_step_s, half_step_s) } else { 0.0 };
+                        let closest = [dr[0] + dv[0] * t, dr[1] + dv[1] * t, dr[2] + dv[2] * t];
+                        if dot(&closest, &closest).sqrt() < threshold_km + LINEAR_MARGIN_KM {
+                            pairs.push((i, j));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    pairs
+}
+
+/// Screens samples `[step_start, step_end)` of the window. Returns how many
+/// (pair, sample) candidates reached refinement alongside the events found.
+pub(crate) fn screen_step_range(
+    props: &[Option<SatProp>],
+    step_start: usize,
+    step_end: usize,
+    params: &ScreeningParams,
+    progress: &ChunkProgress,
+) -> (usize, Vec<ConjunctionEventOut>) {
+    let (window_start_unix_ms, window_end_unix_ms) = (params.start_unix_ms, params.end_unix_ms());
+    let threshold_km = params.miss_threshold_km;
+    let per_step: Vec<(usize, Vec<ConjunctionEventOut>)> = (step_start..step_end.min(grid_steps(params)))
+        .into_par_iter()
+        .map(|k| {
+            if progress.is_cancelled() {
+                return (0, Vec::new());
+            }
+            let t = window_start_unix_ms + k as f64 * GRID_STEP_MS;
+            let states: Vec<_> = props.iter().map(|p| p.as_ref()?.eci_state(t)).collect();
+            let candidates = candidate_pairs(&states, threshold_km);
+            let lo = (t - GRID_STEP_MS / 2.0).max(window_start_unix_ms);
+            let hi = (t + GRID_STEP_MS / 2.0).min(window_end_unix_ms);
+
+            let mut events = Vec::new();
+            for &(i, j) in &candidates {
+                let (Some(pa), Some(pb)) = (&props[i], &props[j]) else { continue };
+                let (Some((dr_lo, dv_lo)), Some((dr_hi, dv_hi))) = (relative_state(pa, pb, lo), relative_state(pa, pb, hi))
+                else {
+                    continue;
+                };
+                let (f_lo, f_hi) = (dot(&dr_lo, &dv_lo), dot(&dr_hi, &dv_hi));
+                // No sign change: the minimum lies in another sample's
+                // interval (or outside the window) and is reported there.
+                if !(f_lo < 0.0 && f_hi >= 0.0) {
+                    continue;
+                }
+                let Some(tca_ms) = find_range_rate_root(pa, pb, lo, hi, f_lo, f_hi) else { continue };
+                let Some(event) = event_at_tca(pa, pb, tca_ms, params.start_unix_ms) else { continue };
+                if f64::from(event.miss_distance_km) < threshold_km {
+                    events.push(ConjunctionEventOut { sat_indices: (i, j), ..event });
+                }
+            }
+            progress.record(1, candidates.len(), events.len());
+            (candidates.len(), events)
+        })
+        .collect();
+
+    let candidates = per_step.iter().map(|(c, _)| c).sum();
+    let events = per_step.into_iter().flat_map(|(_, e)| e).collect();
+    (candidates, events)
+}
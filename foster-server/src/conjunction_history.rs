@@ -0,0 +1,370 @@
+//! Screening history: every screening ever run stays in
+//! `conjunction_screenings`/`conjunction_events`, but `GET /api/conjunction`
+//! only ever shows the newest public one. These routes page through the
+//! rest, `custom` ones included.
+//!
+//! - `GET /api/conjunction/screenings` lists screenings newest first,
+//!   keyset-paginated on id (`before_id` is the previous page's
+//!   `next_before_id`, stable while new screenings are being added), with
+//!   optional `status`, `mode`, `group` and `triggered_by` (`manual`,
+//!   `custom` or `schedule`) filters.
+//! - `GET /api/conjunction/screenings/:id/events` pages one screening's
+//!   events with the same sort/filter parameters as `GET /api/conjunction`
+//!   (`EventQuery`), plus `limit`/`offset`.
+//! - `GET /api/conjunction/screenings/:id/diff?against=<id>` compares a
+//!   screening with an earlier one. Screenings have no notion of event
+//!   identity, so one is inferred: an event in the later screening is the
+//!   same close approach as one in the earlier if it's the same pair of
+//!   objects with a TCA within `tca_tolerance_s` (new element sets move a
+//!   TCA by seconds; the same pair's next approach is most of an orbit
+//!   away). That sorts events into `new`, `persisting` (with how TCA, miss
+//!   distance and Pc moved) and `resolved`. An earlier event only counts as
+//!   resolved if its TCA falls inside the later screening's window — one
+//!   that's already in the past, or beyond the later window, was never
+//!   looked for again, and is listed separately as `out_of_window`.
+
+use crate::conjunction::{
+    expire_stale_screenings, load_events, row_fetch_warnings, row_hoots_rejections, row_params, ConjunctionAppState, ConjunctionEventOut, EventQuery,
+};
+use crate::conjunction_hoots::HootsRejections;
+use crate::conjunction_params::ScreeningParams;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::collections::HashMap;
+
+const DEFAULT_SCREENINGS_PAGE: i64 = 20;
+const MAX_SCREENINGS_PAGE: i64 = 100;
+const MAX_EVENTS_PAGE: i64 = 500;
+const DEFAULT_TCA_TOLERANCE_S: f64 = 300.0;
+const MAX_TCA_TOLERANCE_S: f64 = 3_600.0;
+
+#[derive(Deserialize)]
+pub struct ScreeningsQuery {
+    limit: Option<i64>,
+    before_id: Option<i64>,
+    status: Option<String>,
+    mode: Option<String>,
+    group: Option<String>,
+    triggered_by: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ScreeningSummary {
+    id: i64,
+    group_name: String,
+    status: String,
+    mode: String,
+    calculated_by: String,
+    triggered_by: String,
+    started_at: Option<chrono::DateTime<chrono::Utc>>,
+    completed_at: Option<chrono::DateTime<chrono::Utc>>,
+    total_pairs: i64,
+    pairs_after_hoots: i64,
+    hoots_rejections: Option<HootsRejections>,
+    events_found: i32,
+    elapsed_ms: i64,
+    error_msg: Option<String>,
+    params: Option<ScreeningParams>,
+    fetch_warnings: Vec<String>,
+}
+
+const SUMMARY_COLUMNS: &str = "id, group_name, status, mode, calculated_by, triggered_by, started_at, completed_at, total_pairs,
+    pairs_after_hoots, events_found, elapsed_ms, error_msg, params, window_start_unix_ms, fetch_warnings,
+    hoots_rejected_altitude, hoots_rejected_orbit_path, hoots_rejected_time";
+
+fn summary_from_row(r: &PgRow) -> ScreeningSummary {
+    ScreeningSummary {
+        id: r.try_get("id").unwrap_or(0),
+        group_name: r.try_get("group_name").unwrap_or_default(),
+        status: r.try_get("status").unwrap_or_default(),
+        mode: r.try_get("mode").unwrap_or_default(),
+        calculated_by: r.try_get("calculated_by").unwrap_or_default(),
+        triggered_by: r.try_get("triggered_by").unwrap_or_default(),
+        started_at: r.try_get("started_at").ok(),
+        completed_at: r.try_get("completed_at").ok().flatten(),
+        total_pairs: r.try_get("total_pairs").unwrap_or(0),
+        pairs_after_hoots: r.try_get("pairs_after_hoots").unwrap_or(0),
+        hoots_rejections: row_hoots_rejections(r),
+        events_found: r.try_get("events_found").unwrap_or(0),
+        elapsed_ms: r.try_get("elapsed_ms").unwrap_or(0),
+        error_msg: r.try_get("error_msg").ok().flatten(),
+        params: row_params(r),
+        fetch_warnings: row_fetch_warnings(r),
+    }
+}
+
+async fn load_summary(pool: &PgPool, screening_id: i64) -> Result<Option<ScreeningSummary>, sqlx::Error> {
+    let row = sqlx::query(&format!("SELECT {SUMMARY_COLUMNS} FROM conjunction_screenings WHERE id = $1"))
+        .bind(screening_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.as_ref().map(summary_from_row))
+}
+
+pub async fn list_screenings(
+    State(state): State<ConjunctionAppState>,
+    Query(query): Query<ScreeningsQuery>,
+) -> Result<Json<Value>, StatusCode> {
+    let pool = &state.pool;
+    expire_stale_screenings(pool).await;
+    let limit = query.limit.unwrap_or(DEFAULT_SCREENINGS_PAGE).clamp(1, MAX_SCREENINGS_PAGE);
+    // One extra row says whether there's a next page.
+    let rows = sqlx::query(&format!(
+        "SELECT {SUMMARY_COLUMNS} FROM conjunction_screenings
+         WHERE ($1::BIGINT IS NULL OR id < $1)
+           AND ($2::TEXT IS NULL OR status = $2)
+           AND ($3::TEXT IS NULL OR mode = $3)
+           AND ($4::TEXT IS NULL OR group_name = $4)
+           AND ($5::TEXT IS NULL OR triggered_by = $5)
+         ORDER BY id DESC
+         LIMIT $6"
+    ))
+    .bind(query.before_id)
+    .bind(query.status.as_deref())
+    .bind(query.mode.as_deref())
+    .bind(query.group.as_deref())
+    .bind(query.triggered_by.as_deref())
+    .bind(limit + 1)
+    .fetch_all(pool)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut screenings: Vec<ScreeningSummary> = rows.iter().map(summary_from_row).collect();
+    let next_before_id = if screenings.len() as i64 > limit {
+        screenings.truncate(limit as usize);
+        screenings.last().map(|s| s.id)
+    } else {
+        None
+    };
+    Ok(Json(json!({ "screenings": screenings, "next_before_id": next_before_id })))
+}
+
+pub async fn list_screening_events(
+    State(state): State<ConjunctionAppState>,
+    Path(screening_id): Path<i64>,
+    Query(query): Query<EventQuery>,
+) -> Result<Json<Value>, StatusCode> {
+    if query.order_by().is_none() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let pool = &state.pool;
+    let summary = load_summary(pool, screening_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let query = query.paged(MAX_EVENTS_PAGE);
+    let (total, events) = load_events(pool, screening_id, &query).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(json!({
+        "screening": summary,
+        "total": total,
+        "limit": query.limit(),
+        "offset": query.offset(),
+        "events": events,
+    })))
+}
+
+#[derive(Deserialize)]
+pub struct DiffQuery {
+    against: i64,
+    tca_tolerance_s: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct PersistingEvent {
+    before: ConjunctionEventOut,
+    after: ConjunctionEventOut,
+    tca_shift_s: f64,
+    miss_change_km: f32,
+    pc_change: f64,
+}
+
+#[derive(Default)]
+struct EventDiff {
+    new: Vec<ConjunctionEventOut>,
+    persisting: Vec<PersistingEvent>,
+    resolved: Vec<ConjunctionEventOut>,
+    out_of_window: Vec<ConjunctionEventOut>,
+}
+
+/// The pair by catalog number where the event has both, else by name
+/// (events stored before NORAD IDs were).
+fn pair_key(e: &ConjunctionEventOut) -> (String, String) {
+    let (a, b) = match (e.sat_a_norad_id, e.sat_b_norad_id) {
+        (Some(a), Some(b)) => (a.to_string(), b.to_string()),
+        _ => (e.sat_a.clone(), e.sat_b.clone()),
+    };
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Matches each later event to the nearest-TCA unmatched earlier event of
+/// the same pair within `tolerance_ms`; `after_window` is the later
+/// screening's `[start, end)`.
+fn diff_events(
+    before: Vec<ConjunctionEventOut>,
+    after: Vec<ConjunctionEventOut>,
+    after_window: (f64, f64),
+    tolerance_ms: f64,
+) -> EventDiff {
+    let mut by_pair: HashMap<(String, String), Vec<usize>> = HashMap::new();
+    for (i, e) in before.iter().enumerate() {
+        by_pair.entry(pair_key(e)).or_default().push(i);
+    }
+
+    let mut matched = vec![false; before.len()];
+    let mut diff = EventDiff::default();
+    for event in after {
+        let nearest = by_pair
+            .get(&pair_key(&event))
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|&i| !matched[i] && (before[i].tca_unix_ms - event.tca_unix_ms).abs() <= tolerance_ms)
+            .min_by(|&i, &j| {
+                let di = (before[i].tca_unix_ms - event.tca_unix_ms).abs();
+                let dj = (before[j].tca_unix_ms - event.tca_unix_ms).abs();
+                di.total_cmp(&dj)
+            });
+        match nearest {
+            Some(i) => {
+                matched[i] = true;
+                let earlier = before[i].clone();
+                diff.persisting.push(PersistingEvent {
+                    tca_shift_s: (event.tca_unix_ms - earlier.tca_unix_ms) / 1000.0,
+                    miss_change_km: event.miss_distance_km - earlier.miss_distance_km,
+                    pc_change: event.collision_probability - earlier.collision_probability,
+                    before: earlier,
+                    after: event,
+                });
+            }
+            None => diff.new.push(event),
+        }
+    }
+
+    let (start, end) = after_window;
+    for (event, _) in before.into_iter().zip(matched).filter(|(_, m)| !m) {
+        if (start..end).contains(&event.tca_unix_ms) {
+            diff.resolved.push(event);
+        } else {
+            diff.out_of_window.push(event);
+        }
+    }
+    diff
+}
+
+/// 404 if either screening doesn't exist, 409 unless both are complete.
+pub async fn diff_screenings(
+    State(state): State<ConjunctionAppState>,
+    Path(screening_id): Path<i64>,
+    Query(query): Query<DiffQuery>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let tolerance_s = query.tca_tolerance_s.unwrap_or(DEFAULT_TCA_TOLERANCE_S);
+    if !(tolerance_s.is_finite() && (0.0..=MAX_TCA_TOLERANCE_S).contains(&tolerance_s)) {
+        return Err((StatusCode::BAD_REQUEST, format!("tca_tolerance_s must be between 0 and {MAX_TCA_TOLERANCE_S}")));
+    }
+    let pool = &state.pool;
+    let internal = |e: sqlx::Error| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+
+    let mut screenings = Vec::new();
+    for id in [query.against, screening_id] {
+        let summary = load_summary(pool, id)
+            .await
+            .map_err(internal)?
+            .ok_or_else(|| (StatusCode::NOT_FOUND, format!("no screening {id}")))?;
+        if summary.status != "complete" {
+            return Err((StatusCode::CONFLICT, format!("screening {id} is {}", summary.status)));
+        }
+        screenings.push(summary);
+    }
+    let window = match &screenings[1].params {
+        Some(params) => (params.start_unix_ms, params.end_unix_ms()),
+        None => return Err((StatusCode::UNPROCESSABLE_ENTITY, format!("screening {screening_id} has no readable window"))),
+    };
+
+    let all = EventQuery::default();
+    let (_, before) = load_events(pool, query.against, &all).await.map_err(internal)?;
+    let (_, after) = load_events(pool, screening_id, &all).await.map_err(internal)?;
+    let diff = diff_events(before, after, window, tolerance_s * 1000.0);
+    Ok(Json(json!({
+        "before": screenings[0],
+        "after": screenings[1],
+        "tca_tolerance_s": tolerance_s,
+        "new": diff.new,
+        "persisting": diff.persisting,
+        "resolved": diff.resolved,
+        "out_of_window": diff.out_of_window,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(sat_a: &str, sat_b: &str, tca_unix_ms: f64, miss_distance_km: f32) -> ConjunctionEventOut {
+        ConjunctionEventOut {
+            id: None,
+            sat_a: sat_a.to_string(),
+            sat_b: sat_b.to_string(),
+            sat_a_norad_id: None,
+            sat_b_norad_id: None,
+            sat_a_designator: None,
+            sat_b_designator: None,
+            sat_a_tle_age_days: None,
+            sat_b_tle_age_days: None,
+            tca_unix_ms,
+            miss_distance_km,
+            rel_velocity_km_s: 10.0,
+            collision_probability: 0.0,
+            miss_radial_km: None,
+            miss_in_track_km: None,
+            miss_cross_track_km: None,
+            approach_angle_deg: None,
+            sat_indices: (0, 0),
+        }
+    }
+
+    #[test]
+    fn diff_follows_a_pair_across_runs_by_tca() {
+        const HOUR: f64 = 3_600_000.0;
+        let before = vec![
+            event("ISS", "DEB 1", 10.0 * HOUR, 4.0),
+            // Same pair, a later approach that the second run no longer sees.
+            event("ISS", "DEB 1", 11.0 * HOUR, 6.0),
+            // Already past when the second run starts.
+            event("SAT X", "SAT Y", 1.0 * HOUR, 2.0),
+        ];
+        let after = vec![
+            // Names in the other order, TCA moved by 20 s.
+            event("DEB 1", "ISS", 10.0 * HOUR + 20_000.0, 1.5),
+            event("ISS", "DEB 2", 12.0 * HOUR, 3.0),
+        ];
+        let diff = diff_events(before, after, (2.0 * HOUR, 26.0 * HOUR), 300_000.0);
+
+        assert_eq!(diff.persisting.len(), 1);
+        assert_eq!(diff.persisting[0].tca_shift_s, 20.0);
+        assert_eq!(diff.persisting[0].miss_change_km, -2.5);
+        assert_eq!(diff.new.len(), 1);
+        assert_eq!(diff.new[0].sat_b, "DEB 2");
+        assert_eq!(diff.resolved.len(), 1);
+        assert_eq!(diff.resolved[0].tca_unix_ms, 11.0 * HOUR);
+        assert_eq!(diff.out_of_window.len(), 1);
+        assert_eq!(diff.out_of_window[0].sat_a, "SAT X");
+    }
+
+    #[test]
+    fn diff_tells_same_named_objects_apart_by_norad_id() {
+        let with_ids = |a: u32, b: u32| ConjunctionEventOut { sat_a_norad_id: Some(a), sat_b_norad_id: Some(b), ..event("STARLINK", "DEBRIS", 1_000.0, 1.0) };
+        let diff = diff_events(vec![with_ids(1, 2)], vec![with_ids(2, 1), with_ids(1, 3)], (0.0, 10_000.0), 300_000.0);
+        assert_eq!(diff.persisting.len(), 1);
+        assert_eq!(diff.new.len(), 1);
+        assert_eq!(diff.new[0].sat_b_norad_id, Some(3));
+    }
+}
@@ -0,0 +1,378 @@
+//! The orbit-path and time stages of the Hoots, Crawford & Roehrich (1984)
+//! pre-filter, run after the perigee/apogee check (`hoots_pass` in
+//! `conjunction.rs`) and before a pair is handed to full propagation.
+//!
+//! Two objects can only come within `D` of each other where each is within
+//! `D` of the other's orbital plane — near the line where the two planes
+//! intersect. An object at radius `r` whose plane meets the other's at
+//! relative inclination `I` is that close only while its argument of
+//! latitude is within `asin(D / (r·sin I))` of the line, on either side.
+//! The orbit-path stage checks whether the two orbits' radii along those
+//! arcs, at the same end of the line, come within `D` of each other at all
+//! (the geometric distance between the orbits); the time stage then checks
+//! whether both objects are actually inside their arcs, at the same end,
+//! at any one time in the window.
+//!
+//! Shape (semi-major axis, eccentricity, inclination) comes from the mean
+//! elements, node and perigee drift from their J2 secular rates; where
+//! each object actually is comes from its SGP4 state at both ends of the
+//! window, so the phase (and an effective anomaly rate that folds in drag)
+//! matches what `propagate_pair` will see. The arcs are widened by how far
+//! the intersection line and each perigee can move in half the window plus
+//! a fixed slack, the radii by `RADIAL_SLACK_KM` and the time windows by
+//! `TIME_SLACK_MS`, so the stages
+//! stay conservative: they only reject pairs full propagation couldn't
+//! have reported. Nearly coplanar pairs (where the line isn't defined)
+//! always pass.
+
+use crate::conjunction::{cross, dot, hoots_pass, SatProp, MU};
+use crate::conjunction_params::ScreeningParams;
+use rayon::prelude::*;
+use serde::Serialize;
+use sgp4::Elements;
+use std::f64::consts::{PI, TAU};
+
+const J2: f64 = 1.082_616e-3;
+/// WGS-72, what SGP4 itself works in.
+const EQUATORIAL_RADIUS_KM: f64 = 6_378.135;
+/// Short-period terms the mean elements leave out, on top of the secular
+/// drift.
+const ANGLE_SLACK_RAD: f64 = 0.5 * PI / 180.0;
+/// The same for radius: SGP4's short-period terms move a LEO object ~10 km
+/// above or below its mean-element orbit, so without this a zero buffer
+/// rejects pairs that really do meet.
+const RADIAL_SLACK_KM: f64 = 20.0;
+const TIME_SLACK_MS: f64 = 120_000.0;
+/// Below this `sin I` the planes are treated as coplanar.
+const MIN_SIN_RELATIVE_INCLINATION: f64 = 1e-6;
+
+/// How many pairs each Hoots stage threw out.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Serialize)]
+pub struct HootsRejections {
+    pub altitude: u64,
+    pub orbit_path: u64,
+    pub time: u64,
+}
+
+impl HootsRejections {
+    pub(crate) fn count(&mut self, stage: HootsStage) {
+        match stage {
+            HootsStage::Altitude => self.altitude += 1,
+            HootsStage::OrbitPath => self.orbit_path += 1,
+            HootsStage::Time => self.time += 1,
+        }
+    }
+
+    pub(crate) fn add(&mut self, other: &HootsRejections) {
+        self.altitude += other.altitude;
+        self.orbit_path += other.orbit_path;
+        self.time += other.time;
+    }
+}
+
+/// The stage that rejected a pair.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HootsStage {
+    Altitude,
+    OrbitPath,
+    Time,
+}
+
+/// The mean elements the stages need, with J2 secular rates; angles in
+/// radians, rates per millisecond.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MeanOrbit {
+    semi_major_km: f64,
+    eccentricity: f64,
+    inclination: f64,
+    raan: f64,
+    arg_perigee: f64,
+    mean_motion: f64,
+    raan_rate: f64,
+    arg_perigee_rate: f64,
+    epoch_unix_ms: f64,
+}
+
+impl MeanOrbit {
+    pub(crate) fn from_elements(elements: &Elements, epoch_unix_ms: f64) -> Self {
+        let mean_motion = elements.mean_motion * TAU / 86_400_000.0;
+        let n_rad_s = mean_motion * 1000.0;
+        let semi_major_km = (MU / (n_rad_s * n_rad_s)).cbrt();
+        let eccentricity = elements.eccentricity;
+        let inclination = elements.inclination.to_radians();
+        let p = semi_major_km * (1.0 - eccentricity * eccentricity);
+        let k = 1.5 * J2 * (EQUATORIAL_RADIUS_KM / p).powi(2) * mean_motion;
+        let cos_i = inclination.cos();
+        Self {
+            semi_major_km,
+            eccentricity,
+            inclination,
+            raan: elements.right_ascension.to_radians(),
+            arg_perigee: elements.argument_of_perigee.to_radians(),
+            mean_motion,
+            raan_rate: -k * cos_i,
+            arg_perigee_rate: 0.5 * k * (5.0 * cos_i * cos_i - 1.0),
+            epoch_unix_ms,
+        }
+    }
+
+    fn raan_at(&self, t_ms: f64) -> f64 {
+        self.raan + self.raan_rate * (t_ms - self.epoch_unix_ms)
+    }
+
+    fn arg_perigee_at(&self, t_ms: f64) -> f64 {
+        self.arg_perigee + self.arg_perigee_rate * (t_ms - self.epoch_unix_ms)
+    }
+
+    /// Orbit normal and ascending-node direction at `t_ms`.
+    fn plane_at(&self, t_ms: f64) -> ([f64; 3], [f64; 3]) {
+        let (raan, i) = (self.raan_at(t_ms), self.inclination);
+        ([i.sin() * raan.sin(), -i.sin() * raan.cos(), i.cos()], [raan.cos(), raan.sin(), 0.0])
+    }
+
+    /// Mean anomaly of the object at `position`, taking the plane and
+    /// perigee at `t_ms`.
+    fn mean_anomaly_of(&self, position: &[f64; 3], t_ms: f64) -> f64 {
+        let (normal, node) = self.plane_at(t_ms);
+        let u = dot(position, &cross(&normal, &node)).atan2(dot(position, &node));
+        mean_from_true(u - self.arg_perigee_at(t_ms), self.eccentricity)
+    }
+
+    fn radius_at(&self, true_anomaly: f64) -> f64 {
+        let e = self.eccentricity;
+        self.semi_major_km * (1.0 - e * e) / (1.0 + e * true_anomaly.cos())
+    }
+}
+
+fn mean_from_true(true_anomaly: f64, e: f64) -> f64 {
+    let eccentric = ((1.0 - e * e).sqrt() * true_anomaly.sin()).atan2(e + true_anomaly.cos());
+    eccentric - e * eccentric.sin()
+}
+
+/// Whether `angle` lies within `half` of `center`, on the circle.
+fn within(angle: f64, center: f64, half: f64) -> bool {
+    ((angle - center + PI).rem_euclid(TAU) - PI).abs() <= half
+}
+
+/// One object over one screening window: where it is along its orbit and
+/// how that orbit's plane sits at the window's midpoint.
+pub(crate) struct OrbitPath {
+    orbit: MeanOrbit,
+    start_ms: f64,
+    end_ms: f64,
+    normal: [f64; 3],
+    node: [f64; 3],
+    arg_perigee: f64,
+    /// Mean anomaly at `start_ms`, and the rate that carries it to where
+    /// SGP4 puts the object at `end_ms`.
+    mean_anomaly: f64,
+    anomaly_rate: f64,
+}
+
+impl OrbitPath {
+    /// `None` if SGP4 can't place the object at both ends of the window;
+    /// such a pair skips these stages.
+    pub(crate) fn over_window(sat: &SatProp, start_ms: f64, end_ms: f64) -> Option<Self> {
+        let orbit = sat.mean_orbit();
+        let (r_start, _) = sat.eci_state(start_ms)?;
+        let (r_end, _) = sat.eci_state(end_ms)?;
+        let m_start = orbit.mean_anomaly_of(&r_start, start_ms);
+        let m_end = orbit.mean_anomaly_of(&r_end, end_ms);
+        let span = end_ms - start_ms;
+        // Whole revolutions come from the mean motion, the remainder from
+        // the two fixes.
+        let swept = (m_end - m_start).rem_euclid(TAU);
+        let revs = ((orbit.mean_motion * span - swept) / TAU).round();
+        let anomaly_rate = if span > 0.0 { (swept + revs * TAU) / span } else { orbit.mean_motion };
+        let mid_ms = start_ms + span / 2.0;
+        let (normal, node) = orbit.plane_at(mid_ms);
+        Some(Self {
+            orbit,
+            start_ms,
+            end_ms,
+            normal,
+            node,
+            arg_perigee: orbit.arg_perigee_at(mid_ms),
+            mean_anomaly: m_start,
+            anomaly_rate,
+        })
+    }
+
+    /// True anomaly of direction `k`, which lies in this orbit's plane.
+    fn true_anomaly_of(&self, k: &[f64; 3]) -> f64 {
+        dot(k, &cross(&self.normal, &self.node)).atan2(dot(k, &self.node)) - self.arg_perigee
+    }
+
+    /// Smallest and largest radius over true anomalies `center ± half`.
+    fn radius_range(&self, center: f64, half: f64) -> (f64, f64) {
+        let ends = [self.orbit.radius_at(center - half), self.orbit.radius_at(center + half)];
+        let perigee = self.orbit.radius_at(0.0);
+        let apogee = self.orbit.radius_at(PI);
+        let min = if half >= PI || within(0.0, center, half) { perigee } else { ends[0].min(ends[1]) };
+        let max = if half >= PI || within(PI, center, half) { apogee } else { ends[0].max(ends[1]) };
+        (min, max)
+    }
+
+    /// Spans of the window during which the true anomaly is within `half`
+    /// of `center`, padded by `TIME_SLACK_MS`.
+    fn time_windows(&self, center: f64, half: f64) -> Vec<(f64, f64)> {
+        if half >= PI || self.anomaly_rate <= 0.0 {
+            return vec![(self.start_ms, self.end_ms)];
+        }
+        let e = self.orbit.eccentricity;
+        let m_from = mean_from_true(center - half, e).rem_euclid(TAU);
+        let m_span = (mean_from_true(center + half, e) - m_from).rem_euclid(TAU);
+        // The first candidate may already be under way at the start.
+        let first = (m_from - self.mean_anomaly).rem_euclid(TAU) - TAU;
+        let mut windows = Vec::new();
+        for rev in 0.. {
+            let from = self.start_ms + (first + rev as f64 * TAU) / self.anomaly_rate;
+            if from > self.end_ms {
+                break;
+            }
+            let to = from + m_span / self.anomaly_rate;
+            if to >= self.start_ms {
+                windows.push((from - TIME_SLACK_MS, to + TIME_SLACK_MS));
+            }
+        }
+        windows
+    }
+
+    /// How far the line of intersection with another plane, and this
+    /// perigee, can move in half the window.
+    fn drift(&self) -> f64 {
+        (self.end_ms - self.start_ms) / 2.0 * self.orbit.arg_perigee_rate.abs()
+    }
+
+    fn raan_drift(&self) -> f64 {
+        (self.end_ms - self.start_ms) / 2.0 * self.orbit.raan_rate.abs()
+    }
+}
+
+/// Every object's `OrbitPath` over the screening window, in `props` order.
+pub(crate) fn orbit_paths(props: &[Option<SatProp>], params: &ScreeningParams) -> Vec<Option<OrbitPath>> {
+    props
+        .par_iter()
+        .map(|p| p.as_ref().and_then(|p| OrbitPath::over_window(p, params.start_unix_ms, params.end_unix_ms())))
+        .collect()
+}
+
+/// All three stages in order; `None` if the pair goes on to propagation.
+pub(crate) fn hoots_filter(
+    a: &SatProp,
+    path_a: Option<&OrbitPath>,
+    b: &SatProp,
+    path_b: Option<&OrbitPath>,
+    params: &ScreeningParams,
+) -> Option<HootsStage> {
+    if !hoots_pass(a, b, params.hoots_buffer_km) {
+        return Some(HootsStage::Altitude);
+    }
+    path_and_time(path_a?, path_b?, params.hoots_buffer_km.max(params.miss_threshold_km))
+}
+
+/// Runs the orbit-path and time stages on a pair that passed the altitude
+/// check; `None` if it survives both. `distance_km` is how close counts.
+fn path_and_time(a: &OrbitPath, b: &OrbitPath, distance_km: f64) -> Option<HootsStage> {
+    let line = cross(&a.normal, &b.normal);
+    let sin_i = dot(&line, &line).sqrt();
+    if sin_i < MIN_SIN_RELATIVE_INCLINATION {
+        return None;
+    }
+    let k = [line[0] / sin_i, line[1] / sin_i, line[2] / sin_i];
+    // The line turns with either node; near-coplanar planes turn it most.
+    let line_drift = (a.raan_drift() + b.raan_drift()) / sin_i;
+    let arc = |path: &OrbitPath| {
+        let near_plane = (distance_km / (path.orbit.radius_at(0.0) * sin_i)).min(1.0).asin();
+        near_plane + line_drift + path.raan_drift() + path.drift() + ANGLE_SLACK_RAD
+    };
+    let (arc_a, arc_b) = (arc(a), arc(b));
+    let (node_a, node_b) = (a.true_anomaly_of(&k), b.true_anomaly_of(&k));
+
+    // Each end of the line where the two orbits come within reach.
+    let reach_km = distance_km + RADIAL_SLACK_KM;
+    let reachable: Vec<(f64, f64)> = [0.0, PI]
+        .into_iter()
+        .map(|end| (node_a + end, node_b + end))
+        .filter(|&(center_a, center_b)| {
+            let (min_a, max_a) = a.radius_range(center_a, arc_a);
+            let (min_b, max_b) = b.radius_range(center_b, arc_b);
+            min_a <= max_b + reach_km && min_b <= max_a + reach_km
+        })
+        .collect();
+    if reachable.is_empty() {
+        return Some(HootsStage::OrbitPath);
+    }
+
+    let together = reachable.into_iter().any(|(center_a, center_b)| {
+        let windows_b = b.time_windows(center_b, arc_b);
+        a.time_windows(center_a, arc_a)
+            .into_iter()
+            .any(|(from_a, to_a)| windows_b.iter().any(|&(from_b, to_b)| from_a <= to_b && from_b <= to_a))
+    });
+    if together {
+        None
+    } else {
+        Some(HootsStage::Time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR_MS: f64 = 3_600_000.0;
+
+    /// An unperturbed orbit over a six-hour window, phased by its mean
+    /// anomaly at the start.
+    fn path(semi_major_km: f64, eccentricity: f64, inclination_deg: f64, raan_deg: f64, mean_anomaly_deg: f64) -> OrbitPath {
+        let orbit = MeanOrbit {
+            semi_major_km,
+            eccentricity,
+            inclination: inclination_deg.to_radians(),
+            raan: raan_deg.to_radians(),
+            arg_perigee: 0.0,
+            mean_motion: (MU / semi_major_km.powi(3)).sqrt() / 1000.0,
+            raan_rate: 0.0,
+            arg_perigee_rate: 0.0,
+            epoch_unix_ms: 0.0,
+        };
+        let (normal, node) = orbit.plane_at(0.0);
+        OrbitPath {
+            orbit,
+            start_ms: 0.0,
+            end_ms: 6.0 * HOUR_MS,
+            normal,
+            node,
+            arg_perigee: 0.0,
+            mean_anomaly: mean_anomaly_deg.to_radians(),
+            anomaly_rate: orbit.mean_motion,
+        }
+    }
+
+    #[test]
+    fn orbit_path_stage_compares_radii_at_the_plane_crossings() {
+        // Equatorial, perigee 7050 km / apogee 7950 km, crossing the polar
+        // plane at true anomaly ±90° — radius 7473 km there.
+        let eccentric = path(7500.0, 0.06, 0.0, 0.0, 0.0);
+        // Inside the altitude band, but 327 km clear where the planes meet.
+        assert_eq!(path_and_time(&eccentric, &path(7800.0, 0.0, 90.0, 90.0, 0.0), 30.0), Some(HootsStage::OrbitPath));
+        assert_ne!(path_and_time(&eccentric, &path(7480.0, 0.0, 90.0, 90.0, 0.0), 30.0), Some(HootsStage::OrbitPath));
+        // With no buffer at all, mean radii 12 km apart may still osculate
+        // into each other.
+        assert_ne!(path_and_time(&eccentric, &path(7485.0, 0.0, 90.0, 90.0, 0.0), 0.0), Some(HootsStage::OrbitPath));
+        // Coplanar orbits have no line of intersection to filter on.
+        assert_eq!(path_and_time(&eccentric, &path(7800.0, 0.0, 0.0, 0.0, 0.0), 30.0), None);
+    }
+
+    #[test]
+    fn time_stage_needs_both_objects_at_the_same_crossing_together() {
+        // Same radius, planes crossing along ±y. The equatorial object
+        // reaches +y a quarter period in; the polar one is there at the
+        // start, so they always cross a quarter period apart...
+        let equatorial = path(7000.0, 0.0, 0.0, 0.0, 0.0);
+        assert_eq!(path_and_time(&equatorial, &path(7000.0, 0.0, 90.0, 90.0, 0.0), 10.0), Some(HootsStage::Time));
+        // ...unless it starts a quarter period back.
+        assert_eq!(path_and_time(&equatorial, &path(7000.0, 0.0, 90.0, 90.0, -90.0), 10.0), None);
+    }
+}
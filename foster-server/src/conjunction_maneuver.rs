@@ -0,0 +1,317 @@
+//! `GET /api/conjunction/events/:id/maneuver` — what an avoidance burn
+//! would do to a stored event. Given one of the event's objects (`object`,
+//! `a` or `b`), a burn time before TCA and an along-track Δv, it reports the
+//! new TCA, miss distance and Pc, and the same over a sweep of Δv values
+//! (`dv_min_m_s`..`dv_max_m_s` in `steps`), so the panel can show how
+//! much of a burn buys how much separation.
+//!
+//! SGP4 only propagates element sets, not a state with a kick added, so the
+//! burned object is flown as SGP4 plus the difference between two Kepler
+//! propagations from the burn: the state with the Δv, minus the state
+//! without it. That keeps every perturbation SGP4 models and adds exactly
+//! the maneuver's effect (to first order — over a few days of lead, J2 and
+//! drag acting on the slightly different orbit are well below the
+//! uncertainty in the TLEs themselves). The other object is untouched.
+//!
+//! TCA is re-found near the original one: range-rate sampled across a
+//! window either side and refined with the same regula falsi as the
+//! screening (`range_rate_root`). An along-track burn mostly shifts timing:
+//! it changes the period by `3·Δv/v` of itself, so after `lead` the object
+//! runs `3·|Δv|·lead/v` ahead or behind — about 40 minutes for the largest
+//! burn allowed (10 m/s a week out). The window is
+//! `SEARCH_MIN_HALF_WINDOW_MS` plus half again that shift, so the moved
+//! encounter stays inside it. Only a genuine range minimum (a range-rate
+//! sign change) counts as the new TCA; if there is none in the window
+//! there's no outcome to report, rather than the window's edge passed off
+//! as one.
+
+use crate::conjunction::{dot, range_rate_root, ConjunctionAppState, SatProp, MU};
+use crate::conjunction_cdm::load_event;
+use crate::conjunction_pc::{collision_probability, ObjectClass};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+const SEARCH_MIN_HALF_WINDOW_MS: f64 = 20.0 * 60_000.0;
+const SEARCH_STEP_MS: f64 = 30_000.0;
+/// Furthest ahead of TCA a burn may be planned; past this the TLEs say
+/// little about where either object will be.
+const MAX_LEAD_MS: f64 = 7.0 * 86_400_000.0;
+const MAX_DELTA_V_M_S: f64 = 10.0;
+const DEFAULT_STEPS: usize = 41;
+const MAX_STEPS: usize = 201;
+
+type State6 = ([f64; 3], [f64; 3]);
+
+/// Two-body propagation of `(r, v)` by `dt_s` seconds with the
+/// eccentric-anomaly form of the f and g functions. `None` for an orbit
+/// that isn't bound.
+fn kepler(r0: &[f64; 3], v0: &[f64; 3], dt_s: f64) -> Option<State6> {
+    let r0_norm = dot(r0, r0).sqrt();
+    let a = 1.0 / (2.0 / r0_norm - dot(v0, v0) / MU);
+    if a <= 0.0 || !a.is_finite() {
+        return None;
+    }
+    let n = (MU / (a * a * a)).sqrt();
+    let sigma0 = dot(r0, v0) / MU.sqrt();
+    // n·dt = ΔE + (σ₀/√a)(1 − cos ΔE) − (1 − r₀/a) sin ΔE, solved by Newton;
+    // the derivative is r/a > 0, so it converges from ΔE = n·dt.
+    let mean = n * dt_s;
+    let mut de = mean;
+    for _ in 0..50 {
+        let f = de + sigma0 / a.sqrt() * (1.0 - de.cos()) - (1.0 - r0_norm / a) * de.sin() - mean;
+        let df = 1.0 + sigma0 / a.sqrt() * de.sin() - (1.0 - r0_norm / a) * de.cos();
+        let step = f / df;
+        de -= step;
+        if step.abs() < 1e-12 {
+            break;
+        }
+    }
+    let r_norm = a + (r0_norm - a) * de.cos() + sigma0 * a.sqrt() * de.sin();
+    let f = 1.0 - a / r0_norm * (1.0 - de.cos());
+    let g = dt_s + (a * a * a / MU).sqrt() * (de.sin() - de);
+    let f_dot = -(MU * a).sqrt() / (r_norm * r0_norm) * de.sin();
+    let g_dot = 1.0 - a / r_norm * (1.0 - de.cos());
+    let r = [0, 1, 2].map(|k| f * r0[k] + g * v0[k]);
+    let v = [0, 1, 2].map(|k| f_dot * r0[k] + g_dot * v0[k]);
+    Some((r, v))
+}
+
+/// An object flown off its element set by an impulsive along-track burn.
+struct Burned<'a> {
+    sat: &'a SatProp,
+    burn_unix_ms: f64,
+    at_burn: State6,
+    /// Velocity right after the burn.
+    kicked: [f64; 3],
+}
+
+impl<'a> Burned<'a> {
+    fn new(sat: &'a SatProp, burn_unix_ms: f64, delta_v_km_s: f64) -> Option<Self> {
+        let (r, v) = sat.eci_state(burn_unix_ms)?;
+        let speed = dot(&v, &v).sqrt();
+        let kicked = [0, 1, 2].map(|k| v[k] * (1.0 + delta_v_km_s / speed));
+        Some(Self { sat, burn_unix_ms, at_burn: (r, v), kicked })
+    }
+
+    fn state(&self, t_ms: f64) -> Option<State6> {
+        let (r, v) = self.sat.eci_state(t_ms)?;
+        if t_ms <= self.burn_unix_ms {
+            return Some((r, v));
+        }
+        let dt_s = (t_ms - self.burn_unix_ms) / 1000.0;
+        let (r0, v0) = &self.at_burn;
+        let (r_kick, v_kick) = kepler(r0, &self.kicked, dt_s)?;
+        let (r_coast, v_coast) = kepler(r0, v0, dt_s)?;
+        Some(([0, 1, 2].map(|k| r[k] + r_kick[k] - r_coast[k]), [0, 1, 2].map(|k| v[k] + v_kick[k] - v_coast[k])))
+    }
+}
+
+#[derive(Serialize, Clone, Copy)]
+struct Outcome {
+    delta_v_m_s: f64,
+    tca_unix_ms: f64,
+    miss_distance_km: f64,
+    rel_velocity_km_s: f64,
+    collision_probability: f64,
+}
+
+/// The closest approach within the search window around `tca_unix_ms`
+/// once `maneuvering` (the event's object `maneuvering_is_a`) has burned;
+/// `None` if the range has no minimum there.
+fn outcome(
+    maneuvering: &SatProp,
+    other: &SatProp,
+    maneuvering_is_a: bool,
+    burn_unix_ms: f64,
+    delta_v_m_s: f64,
+    tca_unix_ms: f64,
+) -> Option<Outcome> {
+    let burned = Burned::new(maneuvering, burn_unix_ms, delta_v_m_s / 1000.0)?;
+    let states = |t: f64| -> Option<(State6, State6)> {
+        let moved = burned.state(t)?;
+        let fixed = other.eci_state(t)?;
+        Some(if maneuvering_is_a { (moved, fixed) } else { (fixed, moved) })
+    };
+    let relative = |t: f64| {
+        let ((ra, va), (rb, vb)) = states(t)?;
+        Some(([0, 1, 2].map(|k| rb[k] - ra[k]), [0, 1, 2].map(|k| vb[k] - va[k])))
+    };
+
+    let speed_km_s = dot(&burned.at_burn.1, &burned.at_burn.1).sqrt();
+    let timing_shift_ms = 3.0 * (delta_v_m_s / 1000.0).abs() * (tca_unix_ms - burn_unix_ms).max(0.0) / speed_km_s;
+    let half_window_ms = SEARCH_MIN_HALF_WINDOW_MS + 1.5 * timing_shift_ms;
+    let from = tca_unix_ms - half_window_ms;
+    let samples: Vec<(f64, f64, f64)> = (0..=(2.0 * half_window_ms / SEARCH_STEP_MS).ceil() as usize)
+        .filter_map(|i| {
+            let t = from + i as f64 * SEARCH_STEP_MS;
+            let (dr, dv) = relative(t)?;
+            Some((t, dot(&dr, &dr).sqrt(), dot(&dr, &dv)))
+        })
+        .collect();
+    // Every range minimum inside the window; the closest is the new TCA.
+    let minima = samples
+        .windows(2)
+        .filter(|w| w[0].2 < 0.0 && w[1].2 >= 0.0)
+        .filter_map(|w| range_rate_root(relative, w[0].0, w[1].0, w[0].2, w[1].2));
+    let distance = |t: f64| relative(t).map(|(dr, _)| dot(&dr, &dr).sqrt());
+    let tca = minima
+        .filter_map(|t| Some((t, distance(t)?)))
+        .min_by(|x, y| x.1.total_cmp(&y.1))?
+        .0;
+
+    let ((ra, va), (rb, vb)) = states(tca)?;
+    let (_, dv) = relative(tca)?;
+    let (class_a, class_b) = if maneuvering_is_a {
+        (ObjectClass::from_name(&maneuvering.name), ObjectClass::from_name(&other.name))
+    } else {
+        (ObjectClass::from_name(&other.name), ObjectClass::from_name(&maneuvering.name))
+    };
+    Some(Outcome {
+        delta_v_m_s,
+        tca_unix_ms: tca,
+        miss_distance_km: distance(tca)?,
+        rel_velocity_km_s: dot(&dv, &dv).sqrt(),
+        collision_probability: collision_probability(&ra, &va, class_a, &rb, &vb, class_b),
+    })
+}
+
+#[derive(Deserialize)]
+pub struct ManeuverQuery {
+    /// `a` (default) or `b`: which of the event's objects burns.
+    object: Option<String>,
+    burn_unix_ms: f64,
+    delta_v_m_s: f64,
+    dv_min_m_s: Option<f64>,
+    dv_max_m_s: Option<f64>,
+    steps: Option<usize>,
+}
+
+impl ManeuverQuery {
+    /// Whether object A burns, and the sweep's Δv values; `Err` names the
+    /// parameter that's out of range.
+    fn validate(&self, tca_unix_ms: f64) -> Result<(bool, Vec<f64>), String> {
+        let maneuvering_is_a = match self.object.as_deref() {
+            None | Some("a") => true,
+            Some("b") => false,
+            Some(other) => return Err(format!("object must be a or b, not {other:?}")),
+        };
+        let lead = tca_unix_ms - self.burn_unix_ms;
+        if !(lead > 0.0 && lead <= MAX_LEAD_MS) {
+            return Err(format!("burn_unix_ms must fall in the {} days before TCA", MAX_LEAD_MS / 86_400_000.0));
+        }
+        let in_range = |dv: f64| dv.is_finite() && dv.abs() <= MAX_DELTA_V_M_S;
+        // A sweep either side of the asked-for burn, twice as far out.
+        let reach = (2.0 * self.delta_v_m_s.abs()).max(0.1);
+        let (lo, hi) = (self.dv_min_m_s.unwrap_or(-reach), self.dv_max_m_s.unwrap_or(reach));
+        if !(in_range(self.delta_v_m_s) && in_range(lo) && in_range(hi) && lo < hi) {
+            return Err(format!("delta-v values must lie within ±{MAX_DELTA_V_M_S} m/s, dv_min_m_s below dv_max_m_s"));
+        }
+        let steps = self.steps.unwrap_or(DEFAULT_STEPS);
+        if !(2..=MAX_STEPS).contains(&steps) {
+            return Err(format!("steps must be between 2 and {MAX_STEPS}"));
+        }
+        let sweep = (0..steps).map(|i| lo + (hi - lo) * i as f64 / (steps - 1) as f64).collect();
+        Ok((maneuvering_is_a, sweep))
+    }
+}
+
+/// 404 for an unknown event, 422 for one whose objects can't be
+/// re-propagated, 400 for a burn out of range.
+pub async fn event_maneuver(
+    State(state): State<ConjunctionAppState>,
+    Path(event_id): Path<i64>,
+    Query(query): Query<ManeuverQuery>,
+) -> Response {
+    let event = match load_event(&state.pool, event_id).await {
+        Ok(event) => event,
+        Err(status) => return status.into_response(),
+    };
+    let (maneuvering_is_a, sweep) = match query.validate(event.tca_unix_ms) {
+        Ok(validated) => validated,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+
+    let analysed = tokio::task::spawn_blocking(move || {
+        let (maneuvering, other) = if maneuvering_is_a { (&event.sat_a, &event.sat_b) } else { (&event.sat_b, &event.sat_a) };
+        let run = |dv: f64| outcome(maneuvering, other, maneuvering_is_a, query.burn_unix_ms, dv, event.tca_unix_ms);
+        let original = run(0.0);
+        let maneuver = run(query.delta_v_m_s);
+        let curve: Vec<Outcome> = sweep.into_iter().filter_map(run).collect();
+        json!({
+            "event_id": event.id,
+            "object": if maneuvering_is_a { "a" } else { "b" },
+            "object_name": maneuvering.name,
+            "burn_unix_ms": query.burn_unix_ms,
+            "stored": { "tca_unix_ms": event.tca_unix_ms, "collision_probability": event.collision_probability },
+            "original": original,
+            "maneuver": maneuver,
+            "curve": curve,
+        })
+    })
+    .await;
+
+    match analysed {
+        Ok(result) => axum::Json(result).into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conjunction::test_support::{polar_crossing_pair, EPOCH_UNIX_MS};
+
+    #[test]
+    fn kepler_returns_to_the_start_after_one_period() {
+        // 7000 km circular, plus a little radial motion.
+        let r0 = [7000.0, 0.0, 0.0];
+        let v0 = [0.1, (MU / 7000.0).sqrt(), 0.0];
+        let a = 1.0 / (2.0 / 7000.0 - dot(&v0, &v0) / MU);
+        let period_s = std::f64::consts::TAU * (a * a * a / MU).sqrt();
+        let (r, v) = kepler(&r0, &v0, period_s).unwrap();
+        assert!((0..3).all(|k| (r[k] - r0[k]).abs() < 1e-6 && (v[k] - v0[k]).abs() < 1e-9), "{r:?} {v:?}");
+        // Half a period later a circular orbit is on the far side.
+        let circular = [0.0, (MU / 7000.0).sqrt(), 0.0];
+        let (r, _) = kepler(&r0, &circular, period_s / 2.0 * (7000.0f64 / a).powf(1.5)).unwrap();
+        assert!((r[0] + 7000.0).abs() < 1e-6 && r[1].abs() < 1e-6, "{r:?}");
+    }
+
+    #[test]
+    fn largest_burn_furthest_out_still_lands_on_a_true_minimum() {
+        // Two polar planes 60° apart meet over the pole at epoch; the first
+        // burns the most allowed, as early as allowed.
+        let (a, b) = polar_crossing_pair();
+        let tca = EPOCH_UNIX_MS;
+        let burn = tca - MAX_LEAD_MS;
+        let original = outcome(&a, &b, true, burn, 0.0, tca).unwrap();
+        assert!(original.miss_distance_km < 1.0 && (original.tca_unix_ms - tca).abs() < 5_000.0);
+
+        let moved = outcome(&a, &b, true, burn, MAX_DELTA_V_M_S, tca).expect("a minimum inside the window");
+        // ~40 minutes of drift: far outside a fixed ±20 minutes.
+        assert!((moved.tca_unix_ms - tca).abs() > SEARCH_MIN_HALF_WINDOW_MS, "{}", moved.tca_unix_ms - tca);
+        assert!(moved.miss_distance_km > 100.0, "{}", moved.miss_distance_km);
+        // A real range minimum: the range-rate vanishes there.
+        let burned = Burned::new(&a, burn, MAX_DELTA_V_M_S / 1000.0).unwrap();
+        let ((ra, va), (rb, vb)) = (burned.state(moved.tca_unix_ms).unwrap(), b.eci_state(moved.tca_unix_ms).unwrap());
+        let (dr, dv) = ([0, 1, 2].map(|k| rb[k] - ra[k]), [0, 1, 2].map(|k| vb[k] - va[k]));
+        assert!((dot(&dr, &dv) / dot(&dr, &dr).sqrt()).abs() < 0.01);
+    }
+
+    #[test]
+    fn sweep_spans_both_sides_of_the_asked_for_burn() {
+        let query = ManeuverQuery {
+            object: None,
+            burn_unix_ms: 0.0,
+            delta_v_m_s: 0.5,
+            dv_min_m_s: None,
+            dv_max_m_s: None,
+            steps: Some(5),
+        };
+        assert_eq!(query.validate(3_600_000.0), Ok((true, vec![-1.0, -0.5, 0.0, 0.5, 1.0])));
+        assert!(query.validate(-1.0).is_err());
+        assert!(ManeuverQuery { object: Some("c".into()), ..query }.validate(3_600_000.0).is_err());
+    }
+}
@@ -0,0 +1,281 @@
+//! Per-screening parameters: which objects, over which window, at what
+//! resolution and against which thresholds. `POST /api/conjunction/start`
+//! takes them as an optional JSON body (an empty body keeps the original
+//! fixed 24h / 5-minute / 10 km / 30 km screen of the mode's groups), every
+//! field checked against the limits below so one request can't schedule a
+//! week-long one-second-step pass over the whole catalog. Even within them
+//! a custom screen is expensive, so only the default one is anonymous (see
+//! `start_screening`).
+//!
+//! The resolved set — defaults filled in, start time pinned — is stored on
+//! the `conjunction_screenings` row (`params`, migration 0019), so every
+//! replica's chunks screen with the same values and a result says how it
+//! was produced.
+
+use crate::conjunction::ScreeningMode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+pub(crate) const DEFAULT_DURATION_HOURS: f64 = 24.0;
+pub(crate) const DEFAULT_STEP_MINUTES: f64 = 5.0;
+pub(crate) const DEFAULT_MISS_THRESHOLD_KM: f64 = 10.0;
+pub(crate) const DEFAULT_HOOTS_BUFFER_KM: f64 = 30.0;
+
+const MAX_GROUPS: usize = 8;
+/// Each ID is its own CelesTrak request.
+const MAX_NORAD_IDS: usize = 100;
+/// TLE accuracy decays by kilometres a day, so a window far from the
+/// current element sets screens noise.
+const MAX_START_OFFSET_DAYS: f64 = 3.0;
+const DURATION_HOURS: (f64, f64) = (1.0, 72.0);
+const STEP_MINUTES: (f64, f64) = (0.5, 10.0);
+const MISS_THRESHOLD_KM: (f64, f64) = (0.1, 50.0);
+const HOOTS_BUFFER_KM: (f64, f64) = (0.0, 200.0);
+
+/// `POST /api/conjunction/start` body. Every field is optional.
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct StartRequest {
+    mode: Option<String>,
+    groups: Option<Vec<String>>,
+    norad_ids: Option<Vec<u32>>,
+    start_unix_ms: Option<f64>,
+    duration_hours: Option<f64>,
+    step_minutes: Option<f64>,
+    miss_threshold_km: Option<f64>,
+    hoots_buffer_km: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScreeningParams {
+    pub groups: Vec<String>,
+    pub norad_ids: Vec<u32>,
+    pub start_unix_ms: f64,
+    pub duration_hours: f64,
+    pub step_minutes: f64,
+    pub miss_threshold_km: f64,
+    pub hoots_buffer_km: f64,
+}
+
+impl ScreeningParams {
+    /// The fixed screen every screening ran before parameters existed;
+    /// also what rows from then read back as.
+    pub fn defaults(mode: ScreeningMode, start_unix_ms: f64) -> Self {
+        Self {
+            groups: mode.default_groups().iter().map(|g| g.to_string()).collect(),
+            norad_ids: Vec::new(),
+            start_unix_ms,
+            duration_hours: DEFAULT_DURATION_HOURS,
+            step_minutes: DEFAULT_STEP_MINUTES,
+            miss_threshold_km: DEFAULT_MISS_THRESHOLD_KM,
+            hoots_buffer_km: DEFAULT_HOOTS_BUFFER_KM,
+        }
+    }
+
+    pub fn step_ms(&self) -> f64 {
+        self.step_minutes * 60_000.0
+    }
+
+    /// Sample count across the window, `[start, start + steps·step)`.
+    pub fn steps(&self) -> usize {
+        (self.duration_hours * 60.0 / self.step_minutes).round() as usize
+    }
+
+    pub fn end_unix_ms(&self) -> f64 {
+        self.start_unix_ms + self.duration_hours * 3_600_000.0
+    }
+
+    /// Key for the one-running-screening-per-target lock: the same catalog
+    /// can't be screened twice at once, different ones can. Groups are
+    /// sorted here, so listing them in another order is still the same
+    /// target; `groups` itself keeps the caller's order for loading.
+    pub fn target_label(&self) -> String {
+        let mut parts = self.groups.clone();
+        parts.sort_unstable();
+        if !self.norad_ids.is_empty() {
+            let ids: Vec<String> = self.norad_ids.iter().map(|id| id.to_string()).collect();
+            parts.push(format!("catnr:{}", ids.join(",")));
+        }
+        parts.join("+")
+    }
+}
+
+fn within(name: &str, value: Option<f64>, default: f64, (min, max): (f64, f64)) -> Result<f64, String> {
+    let value = value.unwrap_or(default);
+    if value.is_finite() && (min..=max).contains(&value) {
+        Ok(value)
+    } else {
+        Err(format!("{name} must be between {min} and {max}"))
+    }
+}
+
+impl StartRequest {
+    /// No field set: the fixed default screen, which anyone may start.
+    pub fn is_default(&self) -> bool {
+        let Self { mode, groups, norad_ids, start_unix_ms, duration_hours, step_minutes, miss_threshold_km, hoots_buffer_km } = self;
+        mode.is_none()
+            && groups.is_none()
+            && norad_ids.is_none()
+            && start_unix_ms.is_none()
+            && duration_hours.is_none()
+            && step_minutes.is_none()
+            && miss_threshold_km.is_none()
+            && hoots_buffer_km.is_none()
+    }
+
+    /// Mode defaults to `fine`; groups default to the mode's own only when
+    /// no NORAD IDs are given either.
+    pub fn resolve(mut self, now_unix_ms: f64) -> Result<(ScreeningMode, ScreeningParams), String> {
+        let mode = match self.mode.as_deref() {
+            None => ScreeningMode::Fine,
+            Some(m) => ScreeningMode::parse(m).ok_or_else(|| format!("unknown mode {m:?}"))?,
+        };
+
+        let mut norad_ids = self.norad_ids.take().unwrap_or_default();
+        norad_ids.sort_unstable();
+        norad_ids.dedup();
+        if norad_ids.len() > MAX_NORAD_IDS {
+            return Err(format!("at most {MAX_NORAD_IDS} norad_ids"));
+        }
+        if norad_ids.contains(&0) {
+            return Err("norad_ids must be positive".to_string());
+        }
+
+        let mut groups = match self.groups.take() {
+            Some(groups) => groups,
+            None if norad_ids.is_empty() => mode.default_groups().iter().map(|g| g.to_string()).collect(),
+            None => Vec::new(),
+        };
+        // Order is kept: it's the order sources load and objects index in.
+        let mut seen = HashSet::new();
+        groups.retain(|g| seen.insert(g.clone()));
+        if groups.len() > MAX_GROUPS {
+            return Err(format!("at most {MAX_GROUPS} groups"));
+        }
+        // Group names end up in the CelesTrak URL.
+        if let Some(bad) = groups
+            .iter()
+            .find(|g| g.is_empty() || g.len() > 32 || !g.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'))
+        {
+            return Err(format!("invalid group {bad:?}"));
+        }
+        if groups.is_empty() && norad_ids.is_empty() {
+            return Err("nothing to screen: give groups or norad_ids".to_string());
+        }
+
+        self.window(groups, norad_ids, now_unix_ms).map(|params| (mode, params))
+    }
+
+    /// For `POST /api/conjunction/screen`, whose objects come in the body
+    /// and are screened against cached catalog `groups` (recorded as the
+    /// params' groups): only the window and thresholds are the caller's to
+    /// set.
+    pub fn resolve_window(self, groups: &[&str], now_unix_ms: f64) -> Result<(ScreeningMode, ScreeningParams), String> {
+        if self.groups.is_some() || self.norad_ids.is_some() {
+            return Err("groups and norad_ids don't apply to an ad-hoc screen".to_string());
+        }
+        let mode = match self.mode.as_deref() {
+            None => ScreeningMode::Fine,
+            Some(m) => match ScreeningMode::parse(m) {
+                Some(ScreeningMode::Grid) => return Err("grid mode doesn't apply to an ad-hoc screen".to_string()),
+                Some(mode) => mode,
+                None => return Err(format!("unknown mode {m:?}")),
+            },
+        };
+        let groups = groups.iter().map(|g| g.to_string()).collect();
+        self.window(groups, Vec::new(), now_unix_ms).map(|params| (mode, params))
+    }
+
+    fn window(&self, groups: Vec<String>, norad_ids: Vec<u32>, now_unix_ms: f64) -> Result<ScreeningParams, String> {
+        let max_offset_ms = MAX_START_OFFSET_DAYS * 86_400_000.0;
+        let start_unix_ms = within(
+            "start_unix_ms",
+            self.start_unix_ms,
+            now_unix_ms,
+            (now_unix_ms - max_offset_ms, now_unix_ms + max_offset_ms),
+        )
+        .map_err(|_| format!("start_unix_ms must be within {MAX_START_OFFSET_DAYS} days of now"))?;
+
+        Ok(ScreeningParams {
+            groups,
+            norad_ids,
+            start_unix_ms,
+            duration_hours: within("duration_hours", self.duration_hours, DEFAULT_DURATION_HOURS, DURATION_HOURS)?,
+            step_minutes: within("step_minutes", self.step_minutes, DEFAULT_STEP_MINUTES, STEP_MINUTES)?,
+            miss_threshold_km: within("miss_threshold_km", self.miss_threshold_km, DEFAULT_MISS_THRESHOLD_KM, MISS_THRESHOLD_KM)?,
+            hoots_buffer_km: within("hoots_buffer_km", self.hoots_buffer_km, DEFAULT_HOOTS_BUFFER_KM, HOOTS_BUFFER_KM)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: f64 = 1_704_067_200_000.0;
+
+    fn request(json: &str) -> Result<(ScreeningMode, ScreeningParams), String> {
+        serde_json::from_str::<StartRequest>(json).map_err(|e| e.to_string())?.resolve(NOW)
+    }
+
+    #[test]
+    fn empty_body_keeps_the_original_screen() {
+        assert!(serde_json::from_str::<StartRequest>("{}").unwrap().is_default());
+        assert!(!serde_json::from_str::<StartRequest>(r#"{"mode": "fine"}"#).unwrap().is_default());
+        let (mode, params) = request("{}").unwrap();
+        assert_eq!(mode, ScreeningMode::Fine);
+        assert_eq!(params, ScreeningParams::defaults(ScreeningMode::Fine, NOW));
+        assert_eq!(params.steps(), 288);
+    }
+
+    #[test]
+    fn norad_ids_alone_replace_the_default_groups() {
+        let (_, params) = request(r#"{"norad_ids": [25544, 25544, 48274]}"#).unwrap();
+        assert!(params.groups.is_empty());
+        assert_eq!(params.norad_ids, vec![25544, 48274]);
+        assert_eq!(params.target_label(), "catnr:25544,48274");
+    }
+
+    #[test]
+    fn repeated_groups_count_once_in_first_seen_order() {
+        let (_, params) = request(r#"{"groups": ["active", "stations", "active"]}"#).unwrap();
+        assert_eq!(params.groups, vec!["active", "stations"]);
+        assert_eq!(params.target_label(), "active+stations");
+    }
+
+    #[test]
+    fn group_order_doesnt_change_the_target() {
+        let (_, params) = request(r#"{"groups": ["stations", "active"], "norad_ids": [25544]}"#).unwrap();
+        assert_eq!(params.groups, vec!["stations", "active"]);
+        assert_eq!(params.target_label(), "active+stations+catnr:25544");
+        assert_eq!(params.target_label(), request(r#"{"groups": ["active", "stations"], "norad_ids": [25544]}"#).unwrap().1.target_label());
+    }
+
+    #[test]
+    fn rejects_out_of_range_and_unknown_fields() {
+        for body in [
+            r#"{"step_minutes": 0.01}"#,
+            r#"{"duration_hours": 1000}"#,
+            r#"{"miss_threshold_km": -1}"#,
+            r#"{"start_unix_ms": 0}"#,
+            r#"{"groups": ["../../etc"]}"#,
+            r#"{"groups": []}"#,
+            r#"{"mode": "exhaustive"}"#,
+            r#"{"threshold": 5}"#,
+        ] {
+            assert!(request(body).is_err(), "{body} accepted");
+        }
+    }
+
+    #[test]
+    fn ad_hoc_screen_takes_only_the_window() {
+        let resolve = |json: &str| serde_json::from_str::<StartRequest>(json).unwrap().resolve_window(&["last-30-days"], NOW);
+        let (mode, params) = resolve(r#"{"mode": "coarse", "miss_threshold_km": 2}"#).unwrap();
+        assert_eq!(mode, ScreeningMode::Coarse);
+        assert_eq!(params.groups, vec!["last-30-days"]);
+        assert_eq!(params.miss_threshold_km, 2.0);
+        for body in [r#"{"groups": ["stations"]}"#, r#"{"norad_ids": [25544]}"#, r#"{"mode": "grid"}"#] {
+            assert!(resolve(body).is_err(), "{body} accepted");
+        }
+    }
+}
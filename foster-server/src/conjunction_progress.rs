@@ -0,0 +1,171 @@
+//! Watching and stopping a running screening.
+//!
+//! `GET /api/conjunction/screenings/:id/progress` is a server-sent event
+//! stream, same shape as `/api/metrics/stream`: once a second it pushes the
+//! screening's progress, then a last event when it settles and closes. The
+//! chunks that make up a screening run on whichever replicas claimed them,
+//! so the numbers can't come from any one process's rayon loop; each
+//! claimant writes its chunk's live counters (`ChunkProgress`) onto the
+//! `conjunction_chunks` row on every heartbeat (migration 0023), and the
+//! stream sums finished chunks' totals with running chunks' counters — on
+//! any replica, a few seconds behind at most. The ETA extrapolates elapsed
+//! time by the fraction of work done, where an anchor counts as the pairs
+//! it opens (early anchors cost more than late ones) and a grid sample as
+//! one.
+//!
+//! `POST /api/conjunction/screenings/:id/cancel` (Basic-Auth, Lighthouse
+//! token) marks the screening and its outstanding chunks `cancelled`, which
+//! releases the target's lock at once. Workers mid-chunk find out on their
+//! next heartbeat, skip the anchors they haven't started and discard what
+//! they have; events from chunks already committed stay on the row.
+
+use crate::conjunction::ConjunctionAppState;
+use crate::lighthouse_auth::require_lighthouse_auth;
+use axum::extract::{Path, State};
+use axum::http::{header::HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use serde_json::{json, Value};
+use sqlx::{PgPool, Row};
+
+const PROGRESS_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Fraction of the work done and, once any is, the seconds left at the
+/// rate so far.
+fn estimate(work_done: i64, work_total: i64, elapsed_s: f64) -> (f64, Option<f64>) {
+    if work_total <= 0 {
+        return (0.0, None);
+    }
+    let fraction = (work_done as f64 / work_total as f64).clamp(0.0, 1.0);
+    let eta_s = (fraction > 0.0).then(|| elapsed_s * (1.0 - fraction) / fraction);
+    (fraction, eta_s)
+}
+
+/// The screening's progress as every replica sees it; `None` if there's no
+/// such screening.
+async fn load_progress(pool: &PgPool, screening_id: i64) -> Option<Value> {
+    let row = sqlx::query(
+        "SELECT s.status, s.mode, s.error_msg, s.total_pairs,
+                EXTRACT(EPOCH FROM COALESCE(s.completed_at, NOW()) - s.started_at)::DOUBLE PRECISION AS elapsed_s,
+                COUNT(c.id) AS chunks_total,
+                COUNT(c.id) FILTER (WHERE c.status = 'complete') AS chunks_done,
+                COALESCE(SUM(c.sat_end - c.sat_start), 0)::BIGINT AS units_total,
+                COALESCE(SUM(CASE WHEN c.status = 'complete' THEN c.sat_end - c.sat_start ELSE c.progress_units END), 0)::BIGINT AS units_done,
+                COALESCE(SUM(c.work_total), 0)::BIGINT AS work_total,
+                COALESCE(SUM(CASE WHEN c.status = 'complete' THEN c.work_total ELSE c.progress_work END), 0)::BIGINT AS work_done,
+                COALESCE(SUM(CASE WHEN c.status = 'complete' THEN c.pairs_screened ELSE c.progress_pairs END), 0)::BIGINT AS pairs_after_hoots,
+                COALESCE(SUM(CASE WHEN c.status = 'complete' THEN c.events_found ELSE c.progress_events END), 0)::BIGINT AS events_found
+         FROM conjunction_screenings s
+         LEFT JOIN conjunction_chunks c ON c.screening_id = s.id
+         WHERE s.id = $1
+         GROUP BY s.id",
+    )
+    .bind(screening_id)
+    .fetch_optional(pool)
+    .await
+    .ok()
+    .flatten()?;
+
+    let status: String = row.try_get("status").ok()?;
+    let mode: String = row.try_get("mode").unwrap_or_default();
+    let elapsed_s: f64 = row.try_get("elapsed_s").unwrap_or(0.0);
+    let (fraction, eta_s) = estimate(row.try_get("work_done").unwrap_or(0), row.try_get("work_total").unwrap_or(0), elapsed_s);
+    Some(json!({
+        "status": status,
+        "mode": mode,
+        "error": row.try_get::<Option<String>, _>("error_msg").ok().flatten(),
+        // Grid screenings split the window, not the anchors.
+        "unit": if mode == "grid" { "sample" } else { "anchor" },
+        "units_done": row.try_get::<i64, _>("units_done").unwrap_or(0),
+        "units_total": row.try_get::<i64, _>("units_total").unwrap_or(0),
+        "chunks_done": row.try_get::<i64, _>("chunks_done").unwrap_or(0),
+        "chunks_total": row.try_get::<i64, _>("chunks_total").unwrap_or(0),
+        "total_pairs": row.try_get::<i64, _>("total_pairs").unwrap_or(0),
+        "pairs_after_hoots": row.try_get::<i64, _>("pairs_after_hoots").unwrap_or(0),
+        "events_found": row.try_get::<i64, _>("events_found").unwrap_or(0),
+        "fraction": if status == "complete" { 1.0 } else { fraction },
+        "elapsed_s": elapsed_s,
+        "eta_s": if status == "running" { eta_s } else { None },
+    }))
+}
+
+/// 404 before the stream starts if there's no such screening.
+pub async fn progress_stream(
+    State(state): State<ConjunctionAppState>,
+    Path(screening_id): Path<i64>,
+) -> Result<Sse<impl futures_util::Stream<Item = Result<Event, std::convert::Infallible>>>, StatusCode> {
+    let pool = state.pool;
+    if load_progress(&pool, screening_id).await.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let interval = tokio::time::interval(PROGRESS_INTERVAL);
+    // State: (pool, ticker, finished). The event that reports a settled
+    // status is the last one.
+    let stream = futures_util::stream::unfold((pool, interval, false), move |(pool, mut interval, finished)| async move {
+        if finished {
+            return None;
+        }
+        interval.tick().await;
+        let progress = load_progress(&pool, screening_id).await.unwrap_or_else(|| json!({ "status": "deleted" }));
+        let settled = progress["status"] != "running";
+        Some((Ok(Event::default().data(progress.to_string())), (pool, interval, settled)))
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::new().interval(std::time::Duration::from_secs(15))))
+}
+
+/// 200 once cancelled; 404 for an unknown screening, 409 for one that
+/// isn't running.
+pub async fn cancel_screening(State(state): State<ConjunctionAppState>, Path(screening_id): Path<i64>, headers: HeaderMap) -> Response {
+    if let Err(rejection) = require_lighthouse_auth(&headers, "conjunction") {
+        return rejection.into_response();
+    }
+
+    let pool = &state.pool;
+    let cancelled = async {
+        let mut tx = pool.begin().await?;
+        let cancelled = sqlx::query(
+            "UPDATE conjunction_screenings SET status = 'cancelled', completed_at = NOW(), error_msg = 'cancelled'
+             WHERE id = $1 AND status = 'running'",
+        )
+        .bind(screening_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected()
+            == 1;
+        sqlx::query("UPDATE conjunction_chunks SET status = 'cancelled' WHERE screening_id = $1 AND status IN ('pending', 'running')")
+            .bind(screening_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok::<bool, sqlx::Error>(cancelled)
+    };
+    match cancelled.await {
+        Ok(true) => StatusCode::OK.into_response(),
+        Ok(false) => {
+            let exists: Option<i64> = sqlx::query_scalar("SELECT id FROM conjunction_screenings WHERE id = $1")
+                .bind(screening_id)
+                .fetch_optional(pool)
+                .await
+                .ok()
+                .flatten();
+            if exists.is_some() { StatusCode::CONFLICT } else { StatusCode::NOT_FOUND }.into_response()
+        }
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eta_extrapolates_the_rate_so_far() {
+        assert_eq!(estimate(0, 0, 10.0), (0.0, None));
+        assert_eq!(estimate(0, 100, 10.0), (0.0, None));
+        let (fraction, eta_s) = estimate(25, 100, 30.0);
+        assert_eq!(fraction, 0.25);
+        assert_eq!(eta_s, Some(90.0));
+    }
+}
@@ -0,0 +1,177 @@
+//! Scheduled conjunction screenings — so the page shows a fresh screen
+//! every morning without anyone pressing Start.
+//!
+//! Every replica runs the same loop (`spawn_scheduler`), waking every
+//! `SCHEDULE_POLL`. Time is cut into cadence-aligned buckets (every
+//! `CONJUNCTION_SCHEDULE_HOURS`, 24 by default, counted from the Unix epoch
+//! so all replicas agree on the boundaries) and replicas race for each one
+//! in `conjunction_schedule_claims` (migration 0020), same INSERT ON
+//! CONFLICT DO NOTHING pattern as `tle_refresh_claims` and
+//! `cluster_audit_claims`, so exactly one of them starts the bucket's
+//! screening. It goes through the same `launch_screening` as
+//! `POST /api/conjunction/start` — same chunk queue, same per-target lock
+//! (a screening of the same target still running means the bucket is
+//! skipped, not queued) — with `triggered_by = 'schedule'` on the row.
+//! `CONJUNCTION_SCHEDULE_REQUEST` optionally holds a start body
+//! (`StartRequest` JSON) for what to screen; by default it's the defaults,
+//! with the window starting at the run.
+//!
+//! The bucket's winner also prunes: screenings (and, by cascade, their
+//! events and chunks) that started more than `CONJUNCTION_RETENTION_DAYS`
+//! (30 by default) ago, except the newest complete one the page would show
+//! (not `custom`), so it never goes empty on a quiet stretch. Old claim rows go with them, but never
+//! one whose bucket hasn't ended (see `claim_retention`): deleting the
+//! current bucket's claim would let it be won, and run, again.
+//!
+//! `CONJUNCTION_SCHEDULE_HOURS=0` turns the scheduler off.
+
+use crate::conjunction::launch_screening;
+use crate::conjunction_params::StartRequest;
+use sqlx::PgPool;
+use std::time::{Duration, SystemTime};
+
+const SCHEDULE_POLL: Duration = Duration::from_secs(60);
+const DEFAULT_CADENCE_HOURS: f64 = 24.0;
+/// Anything tighter and consecutive screenings of the full catalog would
+/// overlap.
+const MIN_CADENCE_HOURS: f64 = 1.0;
+const DEFAULT_RETENTION_DAYS: f64 = 30.0;
+
+#[derive(Debug)]
+struct ScheduleConfig {
+    cadence: Duration,
+    retention_days: f64,
+    request: String,
+}
+
+impl ScheduleConfig {
+    /// `None` when the scheduler is switched off; a malformed value is an
+    /// error, reported once at boot, rather than a guess.
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Option<Self>, String> {
+        let number = |name: &str, default: f64| match var(name) {
+            None => Ok(default),
+            Some(v) => v.trim().parse::<f64>().ok().filter(|v| v.is_finite() && *v >= 0.0).ok_or(format!("{name}={v:?} is not a number")),
+        };
+        let cadence_hours = number("CONJUNCTION_SCHEDULE_HOURS", DEFAULT_CADENCE_HOURS)?;
+        if cadence_hours == 0.0 {
+            return Ok(None);
+        }
+        let retention_days = number("CONJUNCTION_RETENTION_DAYS", DEFAULT_RETENTION_DAYS)?;
+        let request = var("CONJUNCTION_SCHEDULE_REQUEST").unwrap_or_else(|| "{}".to_string());
+        // Checked once up front so a bad body is reported at boot, not on
+        // every bucket.
+        serde_json::from_str::<StartRequest>(&request).map_err(|e| format!("CONJUNCTION_SCHEDULE_REQUEST: {e}"))?;
+        Ok(Some(Self {
+            cadence: Duration::from_secs_f64(cadence_hours.max(MIN_CADENCE_HOURS) * 3600.0),
+            retention_days,
+            request,
+        }))
+    }
+
+    /// How long claim rows are kept: the screening retention, but at least
+    /// one cadence, so the current bucket's claim survives until the bucket
+    /// is over however short the retention.
+    fn claim_retention(&self) -> Duration {
+        self.cadence.max(Duration::from_secs_f64(self.retention_days * 86_400.0))
+    }
+}
+
+/// Try to atomically claim the current bucket. Returns true if this replica
+/// won the race — same pattern as `try_claim_tle_refresh`.
+async fn try_claim_bucket(pool: &PgPool, cadence: Duration) -> bool {
+    let result = sqlx::query(
+        "INSERT INTO conjunction_schedule_claims (bucket) \
+         VALUES (to_timestamp(floor(extract(epoch FROM NOW())::DOUBLE PRECISION / $1) * $1)) \
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(cadence.as_secs_f64())
+    .execute(pool)
+    .await;
+
+    matches!(result, Ok(r) if r.rows_affected() == 1)
+}
+
+async fn prune(pool: &PgPool, config: &ScheduleConfig) {
+    let result = sqlx::query(
+        "DELETE FROM conjunction_screenings
+         WHERE status <> 'running'
+           AND started_at < NOW() - make_interval(secs => $1 * 86400)
+           AND id IS DISTINCT FROM (SELECT MAX(id) FROM conjunction_screenings WHERE status = 'complete' AND triggered_by <> 'custom')",
+    )
+    .bind(config.retention_days)
+    .execute(pool)
+    .await;
+    match result {
+        Ok(r) if r.rows_affected() > 0 => println!("Conjunction retention: pruned {} screenings", r.rows_affected()),
+        Ok(_) => {}
+        Err(e) => eprintln!("Conjunction retention prune failed: {e}"),
+    }
+    let _ = sqlx::query("DELETE FROM conjunction_schedule_claims WHERE bucket < NOW() - make_interval(secs => $1)")
+        .bind(config.claim_retention().as_secs_f64())
+        .execute(pool)
+        .await;
+}
+
+async fn run_bucket(pool: &PgPool, config: &ScheduleConfig) {
+    let now_ms = SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis() as f64;
+    let resolved = serde_json::from_str::<StartRequest>(&config.request).map_err(|e| e.to_string()).and_then(|r| r.resolve(now_ms));
+    match resolved {
+        Ok((mode, params)) => match launch_screening(pool, mode, params, "schedule").await {
+            Ok(Some(id)) => println!("Scheduled conjunction screening {id} started"),
+            Ok(None) => println!("Scheduled conjunction screening skipped: same target or too many others still running"),
+            Err(e) => eprintln!("Scheduled conjunction screening failed to start: {e}"),
+        },
+        Err(e) => eprintln!("Scheduled conjunction screening not started: {e}"),
+    }
+    prune(pool, config).await;
+}
+
+pub fn spawn_scheduler(pool: PgPool) {
+    let config = match ScheduleConfig::from_vars(|name| std::env::var(name).ok()) {
+        Ok(Some(config)) => config,
+        Ok(None) => return,
+        Err(e) => {
+            eprintln!("Conjunction scheduler disabled: {e}");
+            return;
+        }
+    };
+    tokio::spawn(async move {
+        loop {
+            if try_claim_bucket(&pool, config.cadence).await {
+                run_bucket(&pool, &config).await;
+            }
+            tokio::time::sleep(SCHEDULE_POLL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config(vars: &[(&str, &str)]) -> Result<Option<ScheduleConfig>, String> {
+        let vars: HashMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        ScheduleConfig::from_vars(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn schedule_config_defaults_and_limits() {
+        let defaults = config(&[]).unwrap().unwrap();
+        assert_eq!(defaults.cadence, Duration::from_secs(24 * 3600));
+        assert_eq!(defaults.retention_days, DEFAULT_RETENTION_DAYS);
+
+        assert!(config(&[("CONJUNCTION_SCHEDULE_HOURS", "0")]).unwrap().is_none());
+        let tight = config(&[("CONJUNCTION_SCHEDULE_HOURS", "0.1")]).unwrap().unwrap();
+        assert_eq!(tight.cadence, Duration::from_secs(3600));
+
+        assert_eq!(defaults.claim_retention(), Duration::from_secs(30 * 86_400));
+        // Retention shorter than the cadence still keeps the current
+        // bucket's claim.
+        let keep_none = config(&[("CONJUNCTION_RETENTION_DAYS", "0")]).unwrap().unwrap();
+        assert_eq!(keep_none.claim_retention(), keep_none.cadence);
+
+        assert!(config(&[("CONJUNCTION_RETENTION_DAYS", "forever")]).is_err());
+        assert!(config(&[("CONJUNCTION_SCHEDULE_REQUEST", r#"{"threshold": 5}"#)]).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+//! The Basic-Auth check for write-side routes: user `jay`, the
+//! `LIGHTHOUSE_UPDATE_TOKEN` as password, compared in constant time. With
+//! no token configured the route is switched off (403) rather than open;
+//! wrong or missing credentials get a 401 with a `WWW-Authenticate`
+//! challenge for `realm`, so a browser prompts for them.
+
+use axum::http::header::{HeaderMap, HeaderValue, WWW_AUTHENTICATE};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use base64::Engine;
+use subtle::ConstantTimeEq;
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+enum AuthError {
+    #[error("invalid credentials.")]
+    InvalidCredentials,
+    #[error("feature is disabled")]
+    Disabled,
+}
+
+fn check(headers: &HeaderMap, token: Option<&str>) -> Result<(), AuthError> {
+    let token = token.ok_or(AuthError::Disabled)?;
+    let header_value = headers.get("authorization").ok_or(AuthError::InvalidCredentials)?;
+    let header_str = header_value.to_str().map_err(|_| AuthError::InvalidCredentials)?;
+    let encoded = header_str.strip_prefix("Basic ").ok_or(AuthError::InvalidCredentials)?;
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|_| AuthError::InvalidCredentials)?;
+    let decoded = String::from_utf8(decoded).map_err(|_| AuthError::InvalidCredentials)?;
+
+    let mut parts = decoded.splitn(2, ':');
+    let username = parts.next().ok_or(AuthError::InvalidCredentials)?;
+    let password = parts.next().ok_or(AuthError::InvalidCredentials)?;
+    let user_ok: bool = username.as_bytes().ct_eq(b"jay").into();
+    let pass_ok: bool = password.as_bytes().ct_eq(token.as_bytes()).into();
+    if !user_ok || !pass_ok {
+        return Err(AuthError::InvalidCredentials);
+    }
+    Ok(())
+}
+
+/// Why a request was turned away, as the response to send instead.
+#[derive(Debug)]
+pub(crate) struct AuthRejection {
+    error: AuthError,
+    realm: &'static str,
+}
+
+impl IntoResponse for AuthRejection {
+    fn into_response(self) -> Response {
+        match self.error {
+            AuthError::Disabled => StatusCode::FORBIDDEN.into_response(),
+            AuthError::InvalidCredentials => {
+                let challenge = HeaderValue::from_str(&format!("Basic realm=\"{}\"", self.realm)).unwrap_or(HeaderValue::from_static("Basic"));
+                (StatusCode::UNAUTHORIZED, [(WWW_AUTHENTICATE, challenge)]).into_response()
+            }
+        }
+    }
+}
+
+pub(crate) fn require_lighthouse_auth(headers: &HeaderMap, realm: &'static str) -> Result<(), AuthRejection> {
+    check(headers, std::env::var("LIGHTHOUSE_UPDATE_TOKEN").ok().as_deref()).map_err(|error| AuthRejection { error, realm })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn basic(credentials: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+        headers.insert("authorization", HeaderValue::from_str(&format!("Basic {encoded}")).unwrap());
+        headers
+    }
+
+    #[test]
+    fn only_jay_with_the_token_gets_through() {
+        assert_eq!(check(&basic("jay:secret"), Some("secret")), Ok(()));
+        assert_eq!(check(&basic("jay:wrong"), Some("secret")), Err(AuthError::InvalidCredentials));
+        assert_eq!(check(&basic("someone:secret"), Some("secret")), Err(AuthError::InvalidCredentials));
+        assert_eq!(check(&HeaderMap::new(), Some("secret")), Err(AuthError::InvalidCredentials));
+        // No token configured: off, whatever the request sends.
+        assert_eq!(check(&basic("jay:secret"), None), Err(AuthError::Disabled));
+    }
+}
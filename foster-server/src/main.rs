@@ -8,13 +8,31 @@
 mod cluster;
 mod cluster_audit;
 mod conjunction;
+mod conjunction_adhoc;
+mod conjunction_cdm;
+mod conjunction_grid;
+mod conjunction_history;
+mod conjunction_hoots;
+mod conjunction_maneuver;
+mod conjunction_params;
+mod conjunction_pc;
+mod conjunction_progress;
+mod conjunction_schedule;
 mod lighthouse;
+mod lighthouse_auth;
 mod photography;
 mod prometheus_client;
 mod request_trace;
 mod satellites;
+mod satellites_detail;
+mod satellites_eclipse;
+mod satellites_frames;
+mod satellites_passes;
+mod satellites_stream;
+mod satellites_wire;
 mod security_audit;
 mod site_middleware;
+mod tle;
 mod visitors;
 
 use axum::routing::{get, post};
@@ -108,6 +126,12 @@ async fn main() {
         .pass("started", "start_screening", "started")
         .build();
     machines.insert("conjunction".to_string(), conjunction_machine);
+    // Every replica works the conjunction_chunks queue, not just the one
+    // that happened to receive the start POST.
+    conjunction::spawn_chunk_worker(pg_pool.clone());
+    // Scheduled screenings: replicas race for each cadence bucket (see
+    // conjunction_schedule.rs), so only one of them starts the run.
+    conjunction_schedule::spawn_scheduler(pg_pool.clone());
 
     // Real 3D satellite tracking — see satellites.rs for the full rationale.
     // Foster only owns the run/pause + playback-speed labels (small,
@@ -156,15 +180,24 @@ async fn main() {
         let steps_up_paused = satellites_runtime.steps_per_tick.clone();
         let steps_down_running = satellites_runtime.steps_per_tick.clone();
         let steps_down_paused = satellites_runtime.steps_per_tick.clone();
+        let live_for_running = satellites_runtime.live.clone();
+        let live_for_paused = satellites_runtime.live.clone();
 
-        fn steps_ctx(steps: u32) -> serde_json::Value {
-            serde_json::json!({ "steps_per_tick": steps })
+        fn steps_ctx(mut ctx: serde_json::Value, steps: u32) -> serde_json::Value {
+            ctx["steps_per_tick"] = steps.into();
+            ctx
+        }
+        // Replay <-> live; the speed only applies to replay.
+        fn toggle_mode(live: &std::sync::atomic::AtomicBool, mut ctx: serde_json::Value) -> serde_json::Value {
+            let now_live = !live.fetch_xor(true, std::sync::atomic::Ordering::Relaxed);
+            ctx["mode"] = if now_live { "live" } else { "replay" }.into();
+            ctx
         }
 
         MachineBuilder::new(
             "satellites",
             "running",
-            serde_json::json!({ "steps_per_tick": 12 }),
+            serde_json::json!({ "steps_per_tick": 12, "mode": "replay" }),
         )
         .state("paused")
         .on("running", "toggle_run", "paused", move |ctx, _| {
@@ -175,27 +208,29 @@ async fn main() {
             running_for_resume.store(true, std::sync::atomic::Ordering::Relaxed);
             Ok(ctx)
         })
-        .on("running", "speed_up", "running", move |_ctx, _| {
+        .on("running", "toggle_mode", "running", move |ctx, _| Ok(toggle_mode(&live_for_running, ctx)))
+        .on("paused", "toggle_mode", "paused", move |ctx, _| Ok(toggle_mode(&live_for_paused, ctx)))
+        .on("running", "speed_up", "running", move |ctx, _| {
             let next = (steps_up_running.load(std::sync::atomic::Ordering::Relaxed) * 2).min(96);
             steps_up_running.store(next, std::sync::atomic::Ordering::Relaxed);
-            Ok(steps_ctx(next))
+            Ok(steps_ctx(ctx, next))
         })
-        .on("paused", "speed_up", "paused", move |_ctx, _| {
+        .on("paused", "speed_up", "paused", move |ctx, _| {
             let next = (steps_up_paused.load(std::sync::atomic::Ordering::Relaxed) * 2).min(96);
             steps_up_paused.store(next, std::sync::atomic::Ordering::Relaxed);
-            Ok(steps_ctx(next))
+            Ok(steps_ctx(ctx, next))
         })
-        .on("running", "speed_down", "running", move |_ctx, _| {
+        .on("running", "speed_down", "running", move |ctx, _| {
             let cur = steps_down_running.load(std::sync::atomic::Ordering::Relaxed);
             let next = (cur / 2).max(1);
             steps_down_running.store(next, std::sync::atomic::Ordering::Relaxed);
-            Ok(steps_ctx(next))
+            Ok(steps_ctx(ctx, next))
         })
-        .on("paused", "speed_down", "paused", move |_ctx, _| {
+        .on("paused", "speed_down", "paused", move |ctx, _| {
             let cur = steps_down_paused.load(std::sync::atomic::Ordering::Relaxed);
             let next = (cur / 2).max(1);
             steps_down_paused.store(next, std::sync::atomic::Ordering::Relaxed);
-            Ok(steps_ctx(next))
+            Ok(steps_ctx(ctx, next))
         })
         .build()
     };
@@ -235,15 +270,21 @@ async fn main() {
 
     let conjunction_router = Router::new()
         .route("/api/conjunction", get(conjunction::get_screening))
-        .route("/api/conjunction/start", post(conjunction::start_screening))
-        .with_state(conjunction::ConjunctionAppState {
-            screening: conjunction::initial_state(),
-            pool: pg_pool.clone(),
-        });
+        .route("/api/conjunction/events/:id/cdm", get(conjunction_cdm::get_event_cdm))
+        .route("/api/conjunction/events/:id/maneuver", get(conjunction_maneuver::event_maneuver))
+        .route("/api/conjunction/screenings", get(conjunction_history::list_screenings))
+        .route("/api/conjunction/screenings/:id/events", get(conjunction_history::list_screening_events))
+        .route("/api/conjunction/screenings/:id/diff", get(conjunction_history::diff_screenings))
+        .route("/api/conjunction/screenings/:id/progress", get(conjunction_progress::progress_stream))
+        .with_state(conjunction::ConjunctionAppState { pool: pg_pool.clone() });
 
     let satellites_router = Router::new()
         .route("/api/satellites", get(satellites::get_positions))
-        .with_state(satellites_runtime);
+        .route("/api/satellites/ids", get(satellites::get_ids))
+        .route("/api/satellites/stream", get(satellites_stream::snapshot_stream))
+        .route("/api/satellites/:norad_id", get(satellites_detail::get_satellite))
+        .route("/api/satellites/:norad_id/passes", get(satellites_passes::get_passes))
+        .with_state(satellites::SatellitesAppState { runtime: satellites_runtime, pool: pg_pool.clone() });
 
     let world_map_router = {
         let svg = world_map_svg.clone();
@@ -261,13 +302,18 @@ async fn main() {
         )
     };
 
-    // Rate limiter for the two Basic-Auth upload endpoints: 5 requests per
-    // minute each, same as the real site's lighthouse-only limiter (now
-    // shared across both upload routes rather than duplicated).
+    // Rate limiter for the Basic-Auth endpoints: 5 requests per minute
+    // each, same as the real site's lighthouse-only limiter (now shared
+    // across the upload routes and the conjunction start/screen/cancel
+    // routes rather than duplicated). Start is limited even for the
+    // anonymous default screen, which only ever needs one click.
     let auth_rate_limiter = RateLimiter::new(5, Duration::from_secs(60));
     let lighthouse_limiter = auth_rate_limiter.clone();
     let security_audit_limiter = auth_rate_limiter.clone();
     let claude_audit_limiter = auth_rate_limiter.clone();
+    let conjunction_start_limiter = auth_rate_limiter.clone();
+    let conjunction_screen_limiter = auth_rate_limiter.clone();
+    let conjunction_cancel_limiter = auth_rate_limiter.clone();
 
     let app = foster_server::router(machines)
         .merge(trace_router)
@@ -299,6 +345,33 @@ async fn main() {
                 }))
                 .with_state(pg_pool.clone()),
         )
+        .route(
+            "/api/conjunction/start",
+            post(conjunction::start_screening)
+                .layer(axum::middleware::from_fn(move |req, next| {
+                    let limiter = conjunction_start_limiter.clone();
+                    async move { limiter.check_middleware(req, next).await }
+                }))
+                .with_state(conjunction::ConjunctionAppState { pool: pg_pool.clone() }),
+        )
+        .route(
+            "/api/conjunction/screen",
+            post(conjunction_adhoc::screen_objects)
+                .layer(axum::middleware::from_fn(move |req, next| {
+                    let limiter = conjunction_screen_limiter.clone();
+                    async move { limiter.check_middleware(req, next).await }
+                }))
+                .with_state(conjunction::ConjunctionAppState { pool: pg_pool.clone() }),
+        )
+        .route(
+            "/api/conjunction/screenings/:id/cancel",
+            post(conjunction_progress::cancel_screening)
+                .layer(axum::middleware::from_fn(move |req, next| {
+                    let limiter = conjunction_cancel_limiter.clone();
+                    async move { limiter.check_middleware(req, next).await }
+                }))
+                .with_state(conjunction::ConjunctionAppState { pool: pg_pool.clone() }),
+        )
         .route(
             "/api/metrics/stream",
             get(cluster::metrics_stream).with_state(pg_pool.clone()),
@@ -12,36 +12,63 @@
 //! that can't be reused directly. Instead this module runs the exact same
 //! `sgp4` crate and math *once per server tick* (shared across every
 //! connected client, not per-tab), caches the resulting position snapshot,
-//! and serves it from a plain polled route
-//! (`static/satellites.js`, same "poll a hand-rolled axum route
-//! independently of Foster's SSE" shape as `conjunction.js`). The client
+//! and pushes each one to every viewer over a hand-rolled server-sent event
+//! route (`satellites_stream.rs`, read by `static/satellites.js`) —
+//! independent of Foster's own SSE, as with `conjunction.js` — besides
+//! serving it from a plain route for one-off requests. The client
 //! interpolates between the two most recent real snapshots for smooth
 //! motion instead of recomputing SGP4 itself. Real data, real orbital
 //! mechanics, real time grid — only *where* the propagation loop runs
 //! changed, forced by the framework, not a fidelity cut.
 //!
+//! Two clocks drive the shared snapshot, switched by the `satellites`
+//! machine's `toggle_mode`: `replay` (the default, as on the real site)
+//! steps through the trailing-24h grid at the chosen speed; `live`
+//! propagates to the wall clock every tick, so the globe shows where
+//! everything is right now. Independently of both, `?time_ms=` propagates
+//! the catalog to any one instant on request — every object whose TLE
+//! epoch is within `TLE_VALIDITY_MS` of it; SGP4 drifts by kilometres a
+//! day beyond that, so older element sets are left out rather than drawn
+//! wrong.
+//!
+//! Each position also says whether the object is sunlit or in the Earth's
+//! penumbra or umbra (`satellites_eclipse.rs`), worked out here alongside
+//! the propagation so every client gets it from the one Sun computed per
+//! tick.
+//!
 //! The actual WebGL2 rendering pipeline (shaders, sphere/equator/pole
 //! geometry, camera matrices, draw calls) is a faithful line-for-line port
 //! of `satellite_renderer.rs` into `static/satellites.js` — that part needed
 //! no architectural adaptation at all, just a language change.
 
+use crate::satellites_eclipse::{illumination, sun_position_km, Illumination};
+use crate::satellites_frames::{render_xyz, Frame};
+use crate::satellites_wire;
+use crate::tle::{self, Catalog, Tle, TleSource};
+use axum::body::Bytes;
+use axum::extract::{Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use base64::Engine;
 use chrono::{DateTime, Utc};
 use rayon::prelude::*;
+use serde::Deserialize;
 use serde_json::{json, Value};
 use sgp4::{Constants, Elements};
-use sqlx::PgPool;
+use sqlx::{PgPool, Row};
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, OnceCell, RwLock};
 
 const EARTH_RADIUS_KM: f64 = 6371.0;
 const J2000_UNIX_MS: f64 = 946_728_000_000.0;
 const STEP_MINUTES: f64 = 5.0;
 const STEPS: usize = 288;
 const STEP_MS: f64 = STEP_MINUTES * 60_000.0;
-const TLE_TTL: Duration = Duration::from_secs(6 * 3600);
 const TICK: Duration = Duration::from_millis(1000);
+/// How far from its epoch a TLE is trusted for an on-request instant.
+const TLE_VALIDITY_MS: f64 = 14.0 * 86_400_000.0;
 
 // Astranis satellite pinning/highlighting (same NORAD IDs and rationale as
 // the real satellite_renderer.rs/satellite_tracker.rs) is applied entirely
@@ -49,11 +76,14 @@ const TICK: Duration = Duration::from_millis(1000);
 // (color + orbit-filter bypass), not the real position data this module
 // computes and serves.
 
-struct RealSat {
-    norad_id: u32,
+pub(crate) struct RealSat {
+    pub(crate) norad_id: u32,
+    pub(crate) name: String,
+    pub(crate) line1: String,
+    pub(crate) line2: String,
     constants: Constants,
     epoch_j2000_years: f64,
-    inclination_deg: f64,
+    pub(crate) inclination_deg: f64,
 }
 
 // sgp4::Constants holds no interior mutability or non-Send state; safe to
@@ -62,172 +92,203 @@ unsafe impl Send for RealSat {}
 unsafe impl Sync for RealSat {}
 
 impl RealSat {
-    fn from_tle(line1: &str, line2: &str) -> Option<Self> {
+    pub(crate) fn from_tle(name: &str, line1: &str, line2: &str) -> Option<Self> {
         let elements = Elements::from_tle(None, line1.as_bytes(), line2.as_bytes()).ok()?;
         let constants = Constants::from_elements(&elements).ok()?;
         let norad_id = line1.get(2..7).and_then(|s| s.trim().parse().ok()).unwrap_or(0);
         Some(Self {
             norad_id,
+            name: name.to_string(),
+            line1: line1.to_string(),
+            line2: line2.to_string(),
             constants,
             epoch_j2000_years: elements.epoch(),
             inclination_deg: elements.inclination,
         })
     }
 
-    /// Real position at an absolute unix-ms timestamp, in the render's
-    /// coordinate convention (x,z equatorial / y polar, Earth-radius units)
-    /// — identical swap and scale to the real `satellite_calculations.rs`.
-    fn position_at(&self, time_ms: f64) -> Option<Value> {
-        let minutes_j2000 = (time_ms - J2000_UNIX_MS) / 60_000.0;
-        let epoch_minutes = self.epoch_j2000_years * 365.25 * 24.0 * 60.0;
-        let tsince = minutes_j2000 - epoch_minutes;
+    pub(crate) fn epoch_unix_ms(&self) -> f64 {
+        J2000_UNIX_MS + self.epoch_j2000_years * 365.25 * 86_400_000.0
+    }
+
+    /// TEME position and velocity (km, km/s) at an absolute unix-ms time.
+    pub(crate) fn teme_state_at(&self, time_ms: f64) -> Option<([f64; 3], [f64; 3])> {
+        let tsince = (time_ms - self.epoch_unix_ms()) / 60_000.0;
         let prediction = self.constants.propagate(sgp4::MinutesSinceEpoch(tsince)).ok()?;
-        let scale = 1.0 / EARTH_RADIUS_KM;
-        let p = prediction.position;
+        Some((prediction.position, prediction.velocity))
+    }
+
+    /// Real position at an absolute unix-ms timestamp in `frame` — by
+    /// default the render's coordinate convention (x,z equatorial / y
+    /// polar, Earth-radius units), identical swap and scale to the real
+    /// `satellite_calculations.rs` — and whether it's in the Earth's shadow
+    /// of the Sun at `sun`.
+    fn position_at(&self, time_ms: f64, sun: &[f64; 3], frame: Frame) -> Option<Value> {
+        let (p, _) = self.teme_state_at(time_ms)?;
+        Some(self.position_value(&p, illumination(&p, sun), time_ms, frame))
+    }
+
+    fn position_value(&self, p: &[f64; 3], illumination: Illumination, time_ms: f64, frame: Frame) -> Value {
         let distance_from_center = (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt();
         let altitude_km = distance_from_center - EARTH_RADIUS_KM;
-        Some(json!({
-            "x": p[0] * scale,
-            "y": p[2] * scale,
-            "z": -(p[1] * scale),
-            "altitude_km": altitude_km,
-            "inclination_deg": self.inclination_deg,
-            "norad_id": self.norad_id,
-        }))
+        let mut position = frame.coordinates(p, time_ms);
+        position["altitude_km"] = altitude_km.into();
+        position["inclination_deg"] = self.inclination_deg.into();
+        position["norad_id"] = self.norad_id.into();
+        position["illumination"] = json!(illumination);
+        position
     }
 }
 
-fn fetch_active_group_blocking() -> Vec<(String, String)> {
-    // CelesTrak throttles repeat downloads of an unchanged group from the
-    // same IP (serves a 403 + plain-text notice instead of TLE data) —
-    // handy for local dev iteration without waiting out the throttle
-    // window each time. Unset in production; the real fetch below is used
-    // there, same as every other group fetch in this migration.
-    let body = if let Ok(path) = std::env::var("SATELLITES_TLE_FIXTURE") {
-        std::fs::read_to_string(path).unwrap_or_default()
-    } else {
-        let url = "https://celestrak.org/NORAD/elements/gp.php?GROUP=active&FORMAT=tle";
-        reqwest::blocking::Client::builder()
-            .user_agent("Mozilla/5.0 (compatible; jaydanhoward-foster-migration)")
-            .timeout(std::time::Duration::from_secs(60))
-            .build()
-            .and_then(|c| c.get(url).send())
-            .and_then(|r| r.text())
-            .unwrap_or_default()
-    };
-    body.lines()
-        .collect::<Vec<_>>()
-        .chunks(3)
-        .filter(|c| c.len() == 3 && c[1].trim_start().starts_with("1 ") && c[2].trim_start().starts_with("2 "))
-        .map(|c| (c[1].to_string(), c[2].to_string()))
-        .collect()
-}
+/// The CelesTrak group tracked — same one the real site fetches.
+const TLE_GROUP: &str = "active";
+/// After a refresh that didn't produce newer TLEs (another replica holds
+/// the claim, or CelesTrak failed), wait this long before asking again.
+const REFRESH_RETRY: Duration = Duration::from_secs(60);
 
-const TLE_CACHE_GROUP: &str = "active";
-
-struct Cache {
+pub(crate) struct Cache {
     /// When these TLEs were actually fetched from CelesTrak — a real wall
     /// clock time (not process-local), so a cache loaded from Postgres on
     /// a fresh pod correctly inherits its true age instead of restarting
-    /// the TLE_TTL countdown from "now" every restart.
+    /// the CATALOG_TTL countdown from "now" every restart.
     fetched_at: DateTime<Utc>,
-    sats: Vec<RealSat>,
+    pub(crate) sats: Vec<RealSat>,
+    /// Identifies this catalog's object order to binary clients
+    /// (`satellites_wire.rs`): `fetched_at` in unix seconds.
+    version: u32,
+    /// `sats`' NORAD IDs and inclinations, encoded once for the wire.
+    ids: Bytes,
     /// Fixed 288-point/5-minute grid covering the trailing 24h as of the
     /// last TLE refresh — same shape as the real site's `time_points`,
     /// just re-anchored on each refresh instead of once per page load.
     time_points: Vec<f64>,
 }
 
-fn build_cache_from_tles(tles: Vec<(String, String)>, fetched_at: DateTime<Utc>) -> Cache {
+impl Cache {
+    pub(crate) fn sat(&self, norad_id: u32) -> Option<&RealSat> {
+        self.sats.iter().find(|s| s.norad_id == norad_id)
+    }
+}
+
+fn build_cache_from_tles(tles: Vec<Tle>, fetched_at: DateTime<Utc>) -> Cache {
     let sats: Vec<RealSat> = tles
         .par_iter()
-        .filter_map(|(l1, l2)| RealSat::from_tle(l1, l2))
+        .filter_map(|(name, l1, l2)| RealSat::from_tle(name, l1, l2))
         .collect();
 
-    let now_ms = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as f64;
-    let start_time = now_ms - 24.0 * 60.0 * 60.0 * 1000.0;
+    let start_time = now_ms() - 24.0 * 60.0 * 60.0 * 1000.0;
     let time_points: Vec<f64> = (0..STEPS).map(|i| start_time + i as f64 * STEP_MS).collect();
 
-    Cache { fetched_at, sats, time_points }
+    let version = fetched_at.timestamp().max(0) as u32;
+    let ids = satellites_wire::encode_ids(version, &sats);
+    Cache { fetched_at, sats, version, ids, time_points }
 }
 
-/// Try to atomically claim the right to do the live CelesTrak refetch for
-/// this minute bucket. Returns true if this replica won the race — same
-/// INSERT ON CONFLICT DO NOTHING pattern as spike_claims/cluster_audit_claims.
-async fn try_claim_tle_refresh(pool: &PgPool) -> bool {
-    let result = sqlx::query(
-        "INSERT INTO tle_refresh_claims (bucket) \
-         VALUES (date_trunc('minute', NOW())) \
-         ON CONFLICT DO NOTHING",
-    )
-    .execute(pool)
-    .await;
-
-    matches!(result, Ok(r) if r.rows_affected() == 1)
-}
+/// Each object's TEME position and lighting at a tick, in `Cache::sats`
+/// order; `None` where SGP4 failed.
+type TickStates = Vec<Option<([f64; 3], Illumination)>>;
 
-async fn load_tle_cache(pool: &PgPool, group: &str) -> Option<(Vec<(String, String)>, DateTime<Utc>)> {
-    let row: (Value, DateTime<Utc>) = sqlx::query_as(
-        "SELECT satellites, fetched_at FROM tle_cache WHERE group_name = $1",
-    )
-    .bind(group)
-    .fetch_optional(pool)
-    .await
-    .ok()
-    .flatten()?;
-    let tles: Vec<(String, String)> = serde_json::from_value(row.0).ok()?;
-    Some((tles, row.1))
+/// One tick's positions, in both wire formats. Binary clients are the
+/// common case, so only the packed form is made with the tick; the JSON is
+/// built from the same states by the first request that wants it and then
+/// shared by every other one until the next tick.
+pub struct Snapshot {
+    /// Tick sequence number, counted from this process's start.
+    pub seq: u64,
+    pub time_ms: f64,
+    pub live: bool,
+    pub binary: Bytes,
+    /// `binary` as `/api/satellites/stream` event data, encoded once here
+    /// rather than once per subscriber.
+    pub(crate) binary_base64: Arc<str>,
+    cache: Arc<Cache>,
+    states: TickStates,
+    json_text: OnceCell<Arc<str>>,
 }
 
-async fn save_tle_cache(pool: &PgPool, group: &str, tles: &[(String, String)], fetched_at: DateTime<Utc>) {
-    let json = serde_json::to_value(tles).unwrap_or(Value::Null);
-    let _ = sqlx::query(
-        "INSERT INTO tle_cache (group_name, satellites, fetched_at) VALUES ($1, $2, $3)
-         ON CONFLICT (group_name) DO UPDATE SET satellites = EXCLUDED.satellites, fetched_at = EXCLUDED.fetched_at",
-    )
-    .bind(group)
-    .bind(json)
-    .bind(fetched_at)
-    .execute(pool)
-    .await;
-}
+impl Snapshot {
+    fn new(seq: u64, time_ms: f64, live: bool, cache: Arc<Cache>, states: TickStates) -> Self {
+        let render: Vec<_> = states.iter().map(|s| s.map(|(p, i)| (render_xyz(&p), i))).collect();
+        let binary = satellites_wire::encode_positions(cache.version, time_ms, &render);
+        let binary_base64 = base64::engine::general_purpose::STANDARD.encode(&binary).into();
+        Self { seq, time_ms, live, binary, binary_base64, cache, states, json_text: OnceCell::new() }
+    }
 
-/// Live CelesTrak fetch + persist to Postgres (best-effort — the in-memory
-/// cache still works fine without a pool for local dev), so the *next*
-/// process restart can skip the ~44s CelesTrak round trip entirely by
-/// reading this back via `load_tle_cache` instead.
-async fn refresh_and_cache(pool: &Option<PgPool>) -> Cache {
-    let tles = tokio::task::spawn_blocking(fetch_active_group_blocking).await.unwrap();
-    let fetched_at = Utc::now();
-    if let Some(pool) = pool {
-        save_tle_cache(pool, TLE_CACHE_GROUP, &tles, fetched_at).await;
+    /// Before any tick: no objects.
+    pub(crate) fn empty(seq: u64) -> Self {
+        Self::new(seq, 0.0, false, Arc::new(build_cache_from_tles(Vec::new(), DateTime::<Utc>::default())), Vec::new())
+    }
+
+    pub(crate) fn mode(&self) -> &'static str {
+        if self.live {
+            "live"
+        } else {
+            "replay"
+        }
+    }
+
+    /// The JSON snapshot, built afresh.
+    pub(crate) fn json(&self) -> Value {
+        let positions: Vec<Value> = self
+            .cache
+            .sats
+            .par_iter()
+            .zip(&self.states)
+            .filter_map(|(s, state)| state.map(|(p, i)| s.position_value(&p, i, self.time_ms, Frame::Render)))
+            .collect();
+        json!({
+            "seq": self.seq,
+            "time_ms": self.time_ms,
+            "mode": self.mode(),
+            "frame": Frame::Render.name(),
+            "count": positions.len(),
+            "positions": positions,
+        })
+    }
+
+    /// The JSON snapshot serialized, built on the first call this tick on
+    /// the blocking pool; later callers await that one build without tying
+    /// up a runtime worker.
+    pub(crate) async fn json_text(self: &Arc<Self>) -> Arc<str> {
+        let snapshot = self.clone();
+        let build = || async move {
+            tokio::task::spawn_blocking(move || snapshot.json().to_string().into()).await.unwrap_or_else(|_| "{}".into())
+        };
+        self.json_text.get_or_init(build).await.clone()
     }
-    tokio::task::spawn_blocking(move || build_cache_from_tles(tles, fetched_at)).await.unwrap()
 }
 
 pub struct SatellitesRuntime {
     pub running: Arc<AtomicBool>,
+    /// Wall-clock propagation instead of the 24h replay.
+    pub live: Arc<AtomicBool>,
     pub steps_per_tick: Arc<AtomicU32>,
-    pub snapshot: Arc<RwLock<Value>>,
+    pub snapshot: Arc<RwLock<Arc<Snapshot>>>,
+    /// Each new snapshot, for `/api/satellites/stream`. Holds only the
+    /// latest, so a subscriber that falls behind skips straight to it.
+    pub(crate) ticks: broadcast::Sender<Arc<Snapshot>>,
+    /// The propagators the loop is ticking, for `?time_ms=` requests.
+    cache: RwLock<Option<Arc<Cache>>>,
 }
 
 impl SatellitesRuntime {
     pub fn new() -> Self {
         Self {
             running: Arc::new(AtomicBool::new(true)),
+            live: Arc::new(AtomicBool::new(false)),
             // 12 steps/tick * 5 sim-min/step @ 1 tick/sec = 1 sim-hour/sec,
             // matching the real site's default ("1.0h/s" shown pre-hydration).
             steps_per_tick: Arc::new(AtomicU32::new(12)),
-            snapshot: Arc::new(RwLock::new(json!({
-                "time_ms": 0.0,
-                "count": 0,
-                "positions": [],
-            }))),
+            snapshot: Arc::new(RwLock::new(Arc::new(Snapshot::empty(0)))),
+            ticks: broadcast::channel(1).0,
+            cache: RwLock::new(None),
         }
     }
+
+    /// The catalog the loop is currently propagating, once one has loaded.
+    pub(crate) async fn catalog(&self) -> Option<Arc<Cache>> {
+        self.cache.read().await.clone()
+    }
 }
 
 impl Default for SatellitesRuntime {
@@ -236,90 +297,275 @@ impl Default for SatellitesRuntime {
     }
 }
 
-/// Spawns the shared background propagation loop: refreshes the real TLE
-/// set every 6h (same TTL as the real site), advances a real time index
-/// through the 288-point grid when running, and re-propagates every
-/// satellite's real position (rayon-parallel, same as conjunction.rs) once
-/// per tick — one computation shared by every connected client, not one per
-/// browser tab.
+async fn build_cache(catalog: Catalog) -> Arc<Cache> {
+    Arc::new(tokio::task::spawn_blocking(move || build_cache_from_tles(catalog.tles, catalog.fetched_at)).await.unwrap())
+}
+
+pub(crate) fn now_ms() -> f64 {
+    std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis() as f64
+}
+
+/// Spawns the shared background propagation loop: keeps the real TLE set
+/// current through the shared catalog (`tle.rs` — 6h TTL, same as the real
+/// site), advances a real time index through the 288-point grid when
+/// running (or follows the wall clock in live mode), and re-propagates
+/// every satellite's real position (rayon-parallel, same as conjunction.rs)
+/// once per tick — one computation shared by every connected client, not
+/// one per browser tab — broadcasting each snapshot to the stream.
 pub fn spawn_background_loop(runtime: Arc<SatellitesRuntime>, pool: Option<PgPool>) {
     tokio::spawn(async move {
+        let source = TleSource::group(TLE_GROUP);
         // A fresh pod otherwise blocks every /api/satellites response on an
         // empty snapshot for as long as the live CelesTrak fetch takes
         // (~44s observed in production) before its first real tick — real
         // user impact, not just a cold-start curiosity (it chained straight
-        // into the page's LCP). Loading the last-known-good TLE set from
-        // Postgres first means a restart only pays that cost if genuinely
-        // nothing's cached yet or the cache has aged past TLE_TTL.
-        let mut cache = match &pool {
-            Some(p) => match load_tle_cache(p, TLE_CACHE_GROUP).await {
-                Some((tles, fetched_at)) => {
-                    tokio::task::spawn_blocking(move || build_cache_from_tles(tles, fetched_at)).await.unwrap()
-                }
-                None => refresh_and_cache(&pool).await,
-            },
-            None => refresh_and_cache(&pool).await,
+        // into the page's LCP). Starting from the last-known-good TLE set in
+        // Postgres, whatever its age, means a restart only pays that cost if
+        // genuinely nothing's cached yet; a stale set is refreshed below
+        // while it's already being served.
+        let cached = match &pool {
+            Some(p) => tle::load_cached(p, &source).await,
+            None => None,
         };
+        let initial = match cached {
+            Some(catalog) => Ok(catalog),
+            None => tle::get_catalog(pool.as_ref(), &source).await,
+        };
+        let mut cache = match initial {
+            Ok(catalog) => build_cache(catalog).await,
+            Err(e) => {
+                eprintln!("Satellites: no TLEs yet: {e}");
+                Arc::new(build_cache_from_tles(Vec::new(), DateTime::<Utc>::default()))
+            }
+        };
+        *runtime.cache.write().await = Some(cache.clone());
+        let mut live_time_ms = now_ms();
         let mut index: usize = 0;
+        let mut seq: u64 = 0;
         let mut ticker = tokio::time::interval(TICK);
+        // The refresh runs beside the tick loop rather than in it, so
+        // positions keep flowing from the stale set while CelesTrak (or the
+        // replica that won the refresh claim) takes its time.
+        let mut refresh: Option<tokio::task::JoinHandle<Result<Catalog, tle::TleError>>> = None;
+        let mut next_refresh = tokio::time::Instant::now();
 
         loop {
             ticker.tick().await;
 
-            if Utc::now().signed_duration_since(cache.fetched_at).to_std().unwrap_or(TLE_TTL) > TLE_TTL {
-                // 3 replicas share this one Postgres-backed cache with no
-                // coordination otherwise — without this claim, all 3 would
-                // independently notice the same staleness and each pay the
-                // ~44s live CelesTrak fetch, and any client polling
-                // /api/satellites during that window could land on a
-                // mid-refresh replica and see an empty response (the exact
-                // stall this cache was built to prevent in the first
-                // place — just recurring every TLE_TTL instead of every
-                // pod restart). Only the replica that wins the claim
-                // refetches; the others wait for it to land in Postgres
-                // and re-read from there instead.
-                if let Some(p) = &pool {
-                    if try_claim_tle_refresh(p).await {
-                        cache = refresh_and_cache(&pool).await;
+            let stale = Utc::now().signed_duration_since(cache.fetched_at).to_std().map_or(true, |age| age >= tle::CATALOG_TTL);
+            if stale && refresh.is_none() && tokio::time::Instant::now() >= next_refresh {
+                let (pool, source) = (pool.clone(), source.clone());
+                refresh = Some(tokio::spawn(async move { tle::get_catalog(pool.as_ref(), &source).await }));
+            }
+            if refresh.as_ref().is_some_and(|h| h.is_finished()) {
+                match refresh.take().unwrap().await {
+                    Ok(Ok(catalog)) if catalog.fetched_at > cache.fetched_at => {
+                        cache = build_cache(catalog).await;
+                        *runtime.cache.write().await = Some(cache.clone());
                         index = 0;
-                    } else {
-                        tokio::time::sleep(Duration::from_secs(5)).await;
-                        if let Some((tles, fetched_at)) = load_tle_cache(p, TLE_CACHE_GROUP).await {
-                            cache = tokio::task::spawn_blocking(move || build_cache_from_tles(tles, fetched_at))
-                                .await
-                                .unwrap();
-                            index = 0;
-                        }
                     }
-                } else {
-                    cache = refresh_and_cache(&pool).await;
-                    index = 0;
+                    Ok(Ok(_)) => next_refresh = tokio::time::Instant::now() + REFRESH_RETRY,
+                    Ok(Err(e)) => {
+                        eprintln!("Satellites: TLE refresh failed: {e}");
+                        next_refresh = tokio::time::Instant::now() + REFRESH_RETRY;
+                    }
+                    Err(e) => {
+                        eprintln!("Satellites: TLE refresh task failed: {e}");
+                        next_refresh = tokio::time::Instant::now() + REFRESH_RETRY;
+                    }
                 }
             }
 
-            if runtime.running.load(Ordering::Relaxed) && !cache.time_points.is_empty() {
+            let running = runtime.running.load(Ordering::Relaxed);
+            let live = runtime.live.load(Ordering::Relaxed);
+            // Paused, live mode holds the instant it was paused at.
+            if live && running {
+                live_time_ms = now_ms();
+            } else if !live && running && !cache.time_points.is_empty() {
                 let steps = runtime.steps_per_tick.load(Ordering::Relaxed).max(1) as usize;
                 index = (index + steps) % cache.time_points.len();
             }
 
-            let time_ms = cache.time_points.get(index).copied().unwrap_or(0.0);
-            let sats = &cache.sats;
-            let positions: Vec<Value> = tokio::task::block_in_place(|| {
-                sats.par_iter().filter_map(|s| s.position_at(time_ms)).collect()
-            });
-
-            let snap = json!({
-                "time_ms": time_ms,
-                "count": positions.len(),
-                "positions": positions,
-            });
-            *runtime.snapshot.write().await = snap;
+            let time_ms = if live { live_time_ms } else { cache.time_points.get(index).copied().unwrap_or(0.0) };
+            let sun = sun_position_km(time_ms);
+            seq += 1;
+            let snapshot = Arc::new(tokio::task::block_in_place(|| {
+                let states = cache
+                    .sats
+                    .par_iter()
+                    .map(|s| s.teme_state_at(time_ms).map(|(p, _)| (p, illumination(&p, &sun))))
+                    .collect();
+                Snapshot::new(seq, time_ms, live, cache.clone(), states)
+            }));
+            *runtime.snapshot.write().await = snapshot.clone();
+            // No subscribers is fine; the snapshot is still polled.
+            let _ = runtime.ticks.send(snapshot);
         }
     });
 }
 
+#[derive(Clone)]
+pub struct SatellitesAppState {
+    pub runtime: Arc<SatellitesRuntime>,
+    pub pool: PgPool,
+}
+
+#[derive(Deserialize)]
+pub struct PositionsQuery {
+    /// Propagate to this instant rather than serve the loop's snapshot;
+    /// 400 if no TLE in the catalog is valid then.
+    time_ms: Option<f64>,
+    /// Output frame for the positions (`satellites_frames.rs`); anything
+    /// but `render` is propagated on request.
+    #[serde(default)]
+    frame: Frame,
+    /// A conjunction event id: the snapshot then also carries a `highlight`
+    /// naming that event's two objects by NORAD ID, with whichever of their
+    /// positions this snapshot has (an object screened from another group
+    /// may not be in `active`).
+    event: Option<i64>,
+    /// With `event`, only its `highlight`, without positions: for a client
+    /// that already follows the stream and just needs to know which two
+    /// objects to mark.
+    #[serde(default)]
+    highlight_only: bool,
+}
+
+fn valid_positions_at(sats: &[RealSat], time_ms: f64, frame: Frame) -> Vec<Value> {
+    let sun = sun_position_km(time_ms);
+    sats.par_iter()
+        .filter(|s| (time_ms - s.epoch_unix_ms()).abs() <= TLE_VALIDITY_MS)
+        .filter_map(|s| s.position_at(time_ms, &sun, frame))
+        .collect()
+}
+
+/// Every catalog object whose TLE is valid at `time_ms`, propagated there.
+async fn positions_at(runtime: &SatellitesRuntime, time_ms: f64, frame: Frame) -> Result<Value, (StatusCode, String)> {
+    let cache = runtime.catalog().await.ok_or((StatusCode::SERVICE_UNAVAILABLE, "no TLEs loaded yet".to_string()))?;
+    let positions = tokio::task::spawn_blocking(move || valid_positions_at(&cache.sats, time_ms, frame))
+        .await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, String::new()))?;
+    if positions.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("no TLE is valid at time_ms (within {} days of its epoch)", TLE_VALIDITY_MS / 86_400_000.0),
+        ));
+    }
+    Ok(json!({
+        "time_ms": time_ms,
+        "mode": "at",
+        "frame": frame.name(),
+        "count": positions.len(),
+        "positions": positions,
+    }))
+}
+
+/// A stored conjunction event's two objects, NORAD IDs first; 404 if there's
+/// no such event.
+async fn event_highlight(pool: &PgPool, event_id: i64) -> Result<([Option<i32>; 2], Value), (StatusCode, String)> {
+    let row = sqlx::query("SELECT sat_a, sat_b, sat_a_norad_id, sat_b_norad_id, tca_unix_ms FROM conjunction_events WHERE id = $1")
+        .bind(event_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, String::new()))?
+        .ok_or((StatusCode::NOT_FOUND, "no such event".to_string()))?;
+    let norad_ids = [
+        row.try_get::<Option<i32>, _>("sat_a_norad_id").ok().flatten(),
+        row.try_get::<Option<i32>, _>("sat_b_norad_id").ok().flatten(),
+    ];
+    let highlight = json!({
+        "event_id": event_id,
+        "sat_a": row.try_get::<String, _>("sat_a").unwrap_or_default(),
+        "sat_b": row.try_get::<String, _>("sat_b").unwrap_or_default(),
+        "norad_ids": norad_ids,
+        "tca_unix_ms": row.try_get::<f64, _>("tca_unix_ms").unwrap_or(0.0),
+    });
+    Ok((norad_ids, highlight))
+}
+
+/// 404 if `event` names no stored conjunction event; 400 for
+/// `highlight_only` without one. An `Accept` naming
+/// `application/octet-stream` gets the loop's binary snapshot
+/// (`satellites_wire.rs`) — 406 if the query asks for anything else.
 pub async fn get_positions(
-    state: axum::extract::State<Arc<SatellitesRuntime>>,
-) -> axum::Json<Value> {
-    axum::Json(state.0.snapshot.read().await.clone())
+    State(state): State<SatellitesAppState>,
+    Query(query): Query<PositionsQuery>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, String)> {
+    if satellites_wire::wants_binary(&headers) {
+        if query.time_ms.is_some() || query.frame != Frame::Render || query.event.is_some() {
+            return Err((
+                StatusCode::NOT_ACCEPTABLE,
+                "the binary snapshot is the current tick in the render frame only".to_string(),
+            ));
+        }
+        let binary = state.runtime.snapshot.read().await.binary.clone();
+        return Ok(([(header::CONTENT_TYPE, satellites_wire::OCTET_STREAM)], binary).into_response());
+    }
+    if query.highlight_only {
+        let event_id = query.event.ok_or((StatusCode::BAD_REQUEST, "highlight_only needs an event".to_string()))?;
+        let (_, highlight) = event_highlight(&state.pool, event_id).await?;
+        return Ok(axum::Json(json!({ "highlight": highlight })).into_response());
+    }
+    let mut snap = match query.time_ms {
+        Some(time_ms) if time_ms.is_finite() => positions_at(&state.runtime, time_ms, query.frame).await?,
+        Some(_) => return Err((StatusCode::BAD_REQUEST, "time_ms must be a number".to_string())),
+        None if query.frame == Frame::Render => {
+            let snapshot = state.runtime.snapshot.read().await.clone();
+            if query.event.is_none() {
+                return Ok(([(header::CONTENT_TYPE, "application/json")], snapshot.json_text().await.to_string()).into_response());
+            }
+            tokio::task::block_in_place(|| snapshot.json())
+        }
+        // The loop's current instant, in another frame.
+        None => {
+            let (time_ms, mode) = {
+                let snapshot = state.runtime.snapshot.read().await;
+                (snapshot.time_ms, snapshot.mode())
+            };
+            let mut snap = positions_at(&state.runtime, time_ms, query.frame).await?;
+            snap["mode"] = mode.into();
+            snap
+        }
+    };
+    if let Some(event_id) = query.event {
+        let (norad_ids, mut highlight) = event_highlight(&state.pool, event_id).await?;
+        let positions: Vec<Value> = snap["positions"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|p| norad_ids.iter().flatten().any(|&id| p["norad_id"] == id))
+            .cloned()
+            .collect();
+        highlight["positions"] = positions.into();
+        snap["highlight"] = highlight;
+    }
+    Ok(axum::Json(snap).into_response())
+}
+
+/// The ID table binary snapshots line up with; 503 before a catalog loads.
+pub async fn get_ids(State(state): State<SatellitesAppState>) -> Result<Response, (StatusCode, String)> {
+    let cache = state.runtime.catalog().await.ok_or((StatusCode::SERVICE_UNAVAILABLE, "no TLEs loaded yet".to_string()))?;
+    Ok(([(header::CONTENT_TYPE, satellites_wire::OCTET_STREAM)], cache.ids.clone()).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn propagates_only_tles_valid_at_the_requested_instant() {
+        let iss = RealSat::from_tle(
+            "ISS (ZARYA)",
+            "1 25544U 98067A   24001.50000000  .00016717  00000-0  10270-3 0  9009",
+            "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537",
+        )
+        .unwrap();
+        // 2024-01-01T12:00Z, the TLE's epoch.
+        let epoch_ms = 1_704_110_400_000.0;
+        let sats = [iss];
+        assert_eq!(valid_positions_at(&sats, epoch_ms + 86_400_000.0, Frame::Render).len(), 1);
+        assert!(valid_positions_at(&sats, epoch_ms - TLE_VALIDITY_MS - 86_400_000.0, Frame::Render).is_empty());
+        assert!(valid_positions_at(&sats, epoch_ms + TLE_VALIDITY_MS + 86_400_000.0, Frame::Ecef).is_empty());
+    }
 }
@@ -0,0 +1,151 @@
+//! `GET /api/satellites/:norad_id` — one object in full. The shared
+//! snapshot is deliberately lean (a position, altitude and inclination per
+//! object, for the ~16k points the globe redraws every tick), so anything
+//! about a single satellite is looked up here instead: its name and TLE,
+//! the mean elements decoded from it, how old it is, and a ground track.
+//!
+//! Everything comes from the same catalog and `RealSat` propagators the
+//! background loop is ticking, so the track lines up with the dot on the
+//! globe. The elements are the TLE's own SGP4 mean elements — Kozai mean
+//! motion, no osculating conversion — which is what the period and the
+//! apogee/perigee heights below are derived from; they can differ from an
+//! instantaneous osculating orbit by a few kilometres.
+//!
+//! The ground track covers one orbital period either side of `time_ms`
+//! (default now), sub-satellite points on the WGS-84 ellipsoid after the
+//! same GMST rotation to Earth-fixed as the CDM export.
+
+use crate::conjunction_cdm::teme_to_itrf;
+use crate::satellites::{now_ms, SatellitesAppState};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sgp4::Elements;
+
+const MU_KM3_S2: f64 = 398_600.441_8;
+const WGS84_A_KM: f64 = 6378.137;
+const WGS84_F: f64 = 1.0 / 298.257_223_563;
+/// Ground-track samples per orbital period.
+const TRACK_POINTS_PER_ORBIT: usize = 90;
+
+/// Geodetic latitude, longitude (radians) and height above the WGS-84
+/// ellipsoid (km) of an Earth-fixed position, by Bowring's iteration.
+pub(crate) fn geodetic(r: &[f64; 3]) -> (f64, f64, f64) {
+    let e2 = WGS84_F * (2.0 - WGS84_F);
+    let lon = r[1].atan2(r[0]);
+    let p = (r[0] * r[0] + r[1] * r[1]).sqrt();
+    let mut lat = r[2].atan2(p * (1.0 - e2));
+    let mut height = 0.0;
+    for _ in 0..5 {
+        let n = WGS84_A_KM / (1.0 - e2 * lat.sin().powi(2)).sqrt();
+        height = if lat.cos().abs() > 1e-9 { p / lat.cos() - n } else { r[2].abs() - n * (1.0 - e2) };
+        lat = r[2].atan2(p * (1.0 - e2 * n / (n + height)));
+    }
+    (lat, lon, height)
+}
+
+/// Earth-fixed position (km) of a point at geodetic latitude and longitude
+/// (radians) and height above the WGS-84 ellipsoid (km).
+pub(crate) fn ecef_from_geodetic(lat: f64, lon: f64, height: f64) -> [f64; 3] {
+    let e2 = WGS84_F * (2.0 - WGS84_F);
+    let n = WGS84_A_KM / (1.0 - e2 * lat.sin().powi(2)).sqrt();
+    [
+        (n + height) * lat.cos() * lon.cos(),
+        (n + height) * lat.cos() * lon.sin(),
+        (n * (1.0 - e2) + height) * lat.sin(),
+    ]
+}
+
+#[derive(Deserialize)]
+pub struct DetailQuery {
+    /// Centre of the ground track; now if absent.
+    time_ms: Option<f64>,
+}
+
+/// Period (minutes), semi-major axis and apogee/perigee heights (km) from
+/// the TLE's mean motion (rev/day) and eccentricity.
+fn orbit_shape(mean_motion_rev_day: f64, eccentricity: f64) -> (f64, f64, f64, f64) {
+    let period_min = 1440.0 / mean_motion_rev_day;
+    let n_rad_s = mean_motion_rev_day * std::f64::consts::TAU / 86_400.0;
+    let a = (MU_KM3_S2 / (n_rad_s * n_rad_s)).cbrt();
+    (period_min, a, a * (1.0 + eccentricity) - WGS84_A_KM, a * (1.0 - eccentricity) - WGS84_A_KM)
+}
+
+/// 404 if the NORAD ID isn't in the tracked catalog; 503 before one loads.
+pub async fn get_satellite(
+    State(state): State<SatellitesAppState>,
+    Path(norad_id): Path<u32>,
+    Query(query): Query<DetailQuery>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let centre_ms = match query.time_ms {
+        Some(t) if t.is_finite() => t,
+        Some(_) => return Err((StatusCode::BAD_REQUEST, "time_ms must be a number".to_string())),
+        None => now_ms(),
+    };
+    let cache = state.runtime.catalog().await.ok_or((StatusCode::SERVICE_UNAVAILABLE, "no TLEs loaded yet".to_string()))?;
+    let sat = cache
+        .sat(norad_id)
+        .ok_or((StatusCode::NOT_FOUND, format!("NORAD {norad_id} is not in the tracked catalog")))?;
+    let elements = Elements::from_tle(None, sat.line1.as_bytes(), sat.line2.as_bytes())
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let (period_min, semi_major_axis_km, apogee_km, perigee_km) = orbit_shape(elements.mean_motion, elements.eccentricity);
+    let period_ms = period_min * 60_000.0;
+    let step_ms = period_ms / TRACK_POINTS_PER_ORBIT as f64;
+    let ground_track: Vec<Value> = (0..=2 * TRACK_POINTS_PER_ORBIT)
+        .filter_map(|k| {
+            let t = centre_ms - period_ms + k as f64 * step_ms;
+            let (r, v) = sat.teme_state_at(t)?;
+            let (r_ecef, _) = teme_to_itrf(&r, &v, t);
+            let (lat, lon, height) = geodetic(&r_ecef);
+            Some(json!({
+                "time_ms": t,
+                "lat_deg": lat.to_degrees(),
+                "lon_deg": lon.to_degrees(),
+                "altitude_km": height,
+            }))
+        })
+        .collect();
+
+    let epoch_ms = sat.epoch_unix_ms();
+    Ok(Json(json!({
+        "norad_id": sat.norad_id,
+        "name": sat.name,
+        "tle": [sat.line1, sat.line2],
+        "epoch_unix_ms": epoch_ms,
+        "tle_age_days": (now_ms() - epoch_ms) / 86_400_000.0,
+        "elements": {
+            "period_min": period_min,
+            "mean_motion_rev_per_day": elements.mean_motion,
+            "semi_major_axis_km": semi_major_axis_km,
+            "eccentricity": elements.eccentricity,
+            "inclination_deg": elements.inclination,
+            "raan_deg": elements.right_ascension,
+            "arg_of_perigee_deg": elements.argument_of_perigee,
+            "mean_anomaly_deg": elements.mean_anomaly,
+            "apogee_altitude_km": apogee_km,
+            "perigee_altitude_km": perigee_km,
+        },
+        "ground_track": ground_track,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn geodetic_inverts_a_known_point_and_iss_shape_is_sane() {
+        // 45°N 10°E, 400 km up.
+        let (lat, lon, h) = (45f64.to_radians(), 10f64.to_radians(), 400.0);
+        let (lat2, lon2, h2) = geodetic(&ecef_from_geodetic(lat, lon, h));
+        assert!((lat2 - lat).abs() < 1e-9 && (lon2 - lon).abs() < 1e-12 && (h2 - h).abs() < 1e-6);
+
+        let (period, _, apogee, perigee) = orbit_shape(15.72125391, 0.0006703);
+        assert!((period - 91.6).abs() < 0.1, "{period}");
+        // a ≈ 6731 km, so 348 × 357 km over the equatorial radius.
+        assert!((perigee - 348.3).abs() < 0.5 && (apogee - 357.3).abs() < 0.5, "{perigee} {apogee}");
+    }
+}
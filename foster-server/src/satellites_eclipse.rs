@@ -0,0 +1,97 @@
+//! Whether a satellite is in sunlight, in the Earth's penumbra or in its
+//! umbra — which is what decides if it can be seen at all from the ground,
+//! and is computed once per tick for the whole snapshot in
+//! `spawn_background_loop` rather than by every browser.
+//!
+//! The Sun comes from the Astronomical Almanac's low-precision series
+//! (Vallado, algorithm 29): about 0.01° over 1950–2050, in the mean equator
+//! of date, which is TEME to well within that. The shadow is the conical
+//! model (Vallado, algorithm 34) with the cone half-angles taken from the
+//! Sun's actual distance, on a spherical Earth of equatorial radius —
+//! refraction and the atmosphere's own dimming are ignored, so the edges
+//! are good to a few seconds of a LEO pass.
+
+use crate::conjunction::dot;
+use serde::Serialize;
+
+const AU_KM: f64 = 149_597_870.7;
+const SUN_RADIUS_KM: f64 = 696_000.0;
+const EARTH_RADIUS_KM: f64 = 6378.137;
+const UNIX_EPOCH_JD: f64 = 2_440_587.5;
+const J2000_JD: f64 = 2_451_545.0;
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Illumination {
+    Sunlit,
+    Penumbra,
+    Umbra,
+}
+
+/// Geocentric Sun position (km) in the mean equator of date.
+pub(crate) fn sun_position_km(unix_ms: f64) -> [f64; 3] {
+    let t = (unix_ms / 86_400_000.0 + UNIX_EPOCH_JD - J2000_JD) / 36_525.0;
+    let mean_longitude = 280.460 + 36_000.771 * t;
+    let m = (357.529_109_2 + 35_999.050_34 * t).to_radians();
+    let ecliptic_longitude = (mean_longitude + 1.914_666_471 * m.sin() + 0.019_994_643 * (2.0 * m).sin()).to_radians();
+    let distance = AU_KM * (1.000_140_612 - 0.016_708_617 * m.cos() - 0.000_139_589 * (2.0 * m).cos());
+    let obliquity = (23.439_291 - 0.013_004_2 * t).to_radians();
+    let (sin_l, cos_l) = ecliptic_longitude.sin_cos();
+    [
+        distance * cos_l,
+        distance * obliquity.cos() * sin_l,
+        distance * obliquity.sin() * sin_l,
+    ]
+}
+
+/// Illumination of an object at `r` (km, same frame as `sun`).
+pub(crate) fn illumination(r: &[f64; 3], sun: &[f64; 3]) -> Illumination {
+    let along_sun = dot(r, sun);
+    if along_sun >= 0.0 {
+        return Illumination::Sunlit;
+    }
+    let sun_distance = dot(sun, sun).sqrt();
+    // Distance behind the Earth along the anti-Sun axis, and off it.
+    let behind = -along_sun / sun_distance;
+    let off_axis = (dot(r, r) - behind * behind).max(0.0).sqrt();
+
+    let penumbra_angle = ((SUN_RADIUS_KM + EARTH_RADIUS_KM) / sun_distance).asin();
+    let penumbra_radius = EARTH_RADIUS_KM / penumbra_angle.cos() + behind * penumbra_angle.tan();
+    if off_axis > penumbra_radius {
+        return Illumination::Sunlit;
+    }
+    let umbra_angle = ((SUN_RADIUS_KM - EARTH_RADIUS_KM) / sun_distance).asin();
+    let umbra_radius = EARTH_RADIUS_KM / umbra_angle.cos() - behind * umbra_angle.tan();
+    if off_axis <= umbra_radius {
+        Illumination::Umbra
+    } else {
+        Illumination::Penumbra
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sun_and_shadow_geometry() {
+        // 2024-03-20T03:06Z, the March equinox: the Sun crosses the equator
+        // heading north, at ecliptic longitude 0.
+        let sun = sun_position_km(1_710_903_960_000.0);
+        let declination = (sun[2] / dot(&sun, &sun).sqrt()).asin().to_degrees();
+        assert!(declination.abs() < 0.02, "{declination}");
+        assert!(sun[0] > 0.99 * AU_KM && sun[1].abs() < 0.001 * AU_KM);
+
+        let unit = sun.map(|c| c / dot(&sun, &sun).sqrt());
+        let at = |along: f64, off: f64| [unit[0] * along, unit[1] * along + off, unit[2] * along];
+        // Noon-side, and directly behind the Earth at LEO and at GEO.
+        assert_eq!(illumination(&at(6_778.0, 0.0), &sun), Illumination::Sunlit);
+        assert_eq!(illumination(&at(-6_778.0, 0.0), &sun), Illumination::Umbra);
+        assert_eq!(illumination(&at(-42_164.0, 0.0), &sun), Illumination::Umbra);
+        // Beside the Earth's limb from behind: a LEO object just outside
+        // the shadow radius, and one inside the penumbral fringe at GEO
+        // (where the umbra's radius is ~6,180 km and the penumbra's ~6,580 km).
+        assert_eq!(illumination(&at(-100.0, 6_500.0), &sun), Illumination::Sunlit);
+        assert_eq!(illumination(&at(-42_164.0, 6_400.0), &sun), Illumination::Penumbra);
+    }
+}
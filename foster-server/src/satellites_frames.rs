@@ -0,0 +1,127 @@
+//! Output frames for `/api/satellites` positions, chosen with `?frame=`.
+//! `render` (the default) is what the globe draws: TEME swapped into its
+//! y-up axes and scaled to Earth radii (see `RealSat::position_at`).
+//! Anything else that wants to know where an object is over the ground
+//! would otherwise have to undo that and redo GMST and the ellipsoid
+//! itself, so the server offers:
+//!
+//! - `ecef` — Earth-fixed `x`/`y`/`z` in km: TEME rotated by GMST (IAU-82),
+//!   the same pseudo-Earth-fixed frame as the CDM export, polar motion
+//!   neglected (metres at LEO).
+//! - `geodetic` — WGS-84 `lat_deg`, `lon_deg` and `height_km` of that
+//!   Earth-fixed position.
+//!
+//! The snapshot the loop builds each tick is in `render` only; a request
+//! for another frame propagates the catalog afresh to the snapshot's
+//! instant (or `time_ms`), which costs what a `?time_ms=` request does.
+
+use crate::conjunction_cdm::teme_to_itrf;
+use crate::satellites_detail::geodetic;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// TEME (km) to the globe's y-up axes in Earth radii.
+pub(crate) fn render_xyz(r_teme: &[f64; 3]) -> [f64; 3] {
+    let scale = 1.0 / EARTH_RADIUS_KM;
+    [r_teme[0] * scale, r_teme[2] * scale, -(r_teme[1] * scale)]
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Frame {
+    #[default]
+    Render,
+    Ecef,
+    Geodetic,
+}
+
+impl Frame {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Frame::Render => "render",
+            Frame::Ecef => "ecef",
+            Frame::Geodetic => "geodetic",
+        }
+    }
+
+    /// The coordinate fields for a TEME position (km) at `time_ms`.
+    pub(crate) fn coordinates(self, r_teme: &[f64; 3], time_ms: f64) -> Value {
+        match self {
+            Frame::Render => {
+                let [x, y, z] = render_xyz(r_teme);
+                json!({ "x": x, "y": y, "z": z })
+            }
+            Frame::Ecef => {
+                let (r, _) = teme_to_itrf(r_teme, &[0.0; 3], time_ms);
+                json!({ "x": r[0], "y": r[1], "z": r[2] })
+            }
+            Frame::Geodetic => {
+                let (r, _) = teme_to_itrf(r_teme, &[0.0; 3], time_ms);
+                let (lat, lon, height) = geodetic(&r);
+                json!({ "lat_deg": lat.to_degrees(), "lon_deg": lon.to_degrees(), "height_km": height })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::satellites::RealSat;
+
+    #[test]
+    fn teme_to_earth_fixed_matches_vallados_verification_vector() {
+        // Vallado et al., "Revisiting Spacetrack Report #3" (AIAA 2006-6753)
+        // and Fundamentals of Astrodynamics, Example 3-15: the TEME state at
+        // 2004-04-06 07:51:28.386009 UTC and its GMST-rotated PEF position.
+        // GMST runs on UT1, 0.4399619 s behind UTC then.
+        let ut1_ms = 1_081_237_887_946.047;
+        let teme = [5094.18016210, 6127.64465950, 6380.34453270];
+        let pef = [-1033.47503130, 7901.30558560, 6380.34453270];
+
+        let ecef = Frame::Ecef.coordinates(&teme, ut1_ms);
+        for (axis, expected) in ["x", "y", "z"].iter().zip(pef) {
+            let got = ecef[axis].as_f64().unwrap();
+            assert!((got - expected).abs() < 0.01, "{axis}: {got} vs {expected}");
+        }
+
+        // Over central Asia, 97.45°E.
+        let geo = Frame::Geodetic.coordinates(&teme, ut1_ms);
+        assert!((geo["lon_deg"].as_f64().unwrap() - 97.45).abs() < 0.1, "{geo}");
+
+        let render = Frame::Render.coordinates(&teme, ut1_ms);
+        assert!((render["y"].as_f64().unwrap() * EARTH_RADIUS_KM - teme[2]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn geodetic_matches_vallados_worked_example() {
+        // Fundamentals of Astrodynamics, Example 3-3: an Earth-fixed
+        // position and its WGS-84 latitude, longitude and height.
+        let (lat, lon, height) = geodetic(&[6524.834, 6862.875, 6448.296]);
+        assert!((lat.to_degrees() - 34.352496).abs() < 1e-5, "{}", lat.to_degrees());
+        assert!((lon.to_degrees() - 46.4464).abs() < 1e-4, "{}", lon.to_degrees());
+        assert!((height - 5085.22).abs() < 0.01, "{height}");
+    }
+
+    #[test]
+    fn propagation_matches_the_sgp4_verification_case() {
+        // SGP4-VER.TLE case 00005 (Vallado et al., AIAA 2006-6753) and its
+        // published TEME state 360 minutes past epoch, through the same
+        // element parsing and epoch arithmetic the catalog uses.
+        let sat = RealSat::from_tle(
+            "00005",
+            "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+            "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+        )
+        .unwrap();
+        let (r, v) = sat.teme_state_at(sat.epoch_unix_ms() + 360.0 * 60_000.0).unwrap();
+        let (r_published, v_published) =
+            ([-7154.03120202, -3783.17682504, -3536.19412294], [4.741887409, -4.151817765, -2.093935425]);
+        for k in 0..3 {
+            assert!((r[k] - r_published[k]).abs() < 1e-3, "r{k}: {} vs {}", r[k], r_published[k]);
+            assert!((v[k] - v_published[k]).abs() < 1e-6, "v{k}: {} vs {}", v[k], v_published[k]);
+        }
+    }
+}
@@ -0,0 +1,279 @@
+//! `GET /api/satellites/:norad_id/passes?lat=&lon=&alt=&days=` — when an
+//! object is above a ground observer's horizon over the next `days` (default
+//! 2, at most 10): acquisition of signal (AOS), culmination (TCA) and loss
+//! of signal (LOS), each with the topocentric azimuth, elevation, range and
+//! range-rate the observer would see. `lat`/`lon` are geodetic degrees,
+//! `alt` metres above the WGS-84 ellipsoid.
+//!
+//! The satellite's TEME state from its `RealSat` SGP4 propagator is rotated
+//! to Earth-fixed by GMST (`teme_to_itrf`, as for the CDM export and ground
+//! track) and expressed in the observer's south-east-zenith frame; azimuth
+//! is from north through east. Range-rate is along the line of sight in the
+//! Earth-fixed frame, positive receding — what a radio Doppler correction
+//! wants.
+//!
+//! Elevation is sampled every `SCAN_STEP_MS`; each horizon crossing is then
+//! bisected to `REFINE_MS` and the culmination found by golden-section
+//! search between them. The scan step is shorter than the briefest LEO pass
+//! worth listing, but a pass that only grazes the horizon for less than it
+//! can be missed. A pass already in progress when the window opens, or
+//! still up when it closes, is left out: its AOS or LOS isn't known. Objects
+//! that never set or never rise (GEO, mostly) have no passes.
+//!
+//! A pass is `visible` — worth going outside for without a radio — if at
+//! some point during it the satellite is sunlit (`satellites_eclipse.rs`)
+//! while the Sun is more than `TWILIGHT_DEG` below the observer's horizon,
+//! checked every `VISIBILITY_STEP_MS` from AOS to LOS.
+
+use crate::conjunction::dot;
+use crate::conjunction_cdm::teme_to_itrf;
+use crate::satellites::{now_ms, RealSat, SatellitesAppState};
+use crate::satellites_detail::ecef_from_geodetic;
+use crate::satellites_eclipse::{illumination, sun_position_km, Illumination};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+const SCAN_STEP_MS: f64 = 20_000.0;
+const REFINE_MS: f64 = 100.0;
+const MAX_DAYS: f64 = 10.0;
+/// Civil twilight: the sky is dark enough to pick out a sunlit satellite.
+const TWILIGHT_DEG: f64 = -6.0;
+const VISIBILITY_STEP_MS: f64 = 10_000.0;
+
+/// A ground observer, with its south-east-zenith basis precomputed.
+pub(crate) struct Observer {
+    position: [f64; 3],
+    south: [f64; 3],
+    east: [f64; 3],
+    zenith: [f64; 3],
+}
+
+impl Observer {
+    /// Geodetic latitude and longitude in degrees, height in km.
+    pub(crate) fn new(lat_deg: f64, lon_deg: f64, height_km: f64) -> Self {
+        let (lat, lon) = (lat_deg.to_radians(), lon_deg.to_radians());
+        let (sin_lat, cos_lat) = lat.sin_cos();
+        let (sin_lon, cos_lon) = lon.sin_cos();
+        Self {
+            position: ecef_from_geodetic(lat, lon, height_km),
+            south: [sin_lat * cos_lon, sin_lat * sin_lon, -cos_lat],
+            east: [-sin_lon, cos_lon, 0.0],
+            zenith: [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat],
+        }
+    }
+
+    /// Elevation (degrees) of an Earth-fixed point.
+    fn elevation_deg_of(&self, r_ecef: &[f64; 3]) -> f64 {
+        let rho = [
+            r_ecef[0] - self.position[0],
+            r_ecef[1] - self.position[1],
+            r_ecef[2] - self.position[2],
+        ];
+        (dot(&rho, &self.zenith) / dot(&rho, &rho).sqrt()).asin().to_degrees()
+    }
+
+    /// Whether the Sun is far enough below the horizon for the observer to
+    /// see a sunlit satellite.
+    fn in_darkness(&self, time_ms: f64) -> bool {
+        let (sun_ecef, _) = teme_to_itrf(&sun_position_km(time_ms), &[0.0; 3], time_ms);
+        self.elevation_deg_of(&sun_ecef) < TWILIGHT_DEG
+    }
+
+    /// What the observer sees of `sat` at `time_ms`.
+    pub(crate) fn look_at(&self, sat: &RealSat, time_ms: f64) -> Option<Look> {
+        let (r, v) = sat.teme_state_at(time_ms)?;
+        let (r_ecef, v_ecef) = teme_to_itrf(&r, &v, time_ms);
+        let rho = [
+            r_ecef[0] - self.position[0],
+            r_ecef[1] - self.position[1],
+            r_ecef[2] - self.position[2],
+        ];
+        let range_km = dot(&rho, &rho).sqrt();
+        let illumination = illumination(&r, &sun_position_km(time_ms));
+        let (s, e, z) = (dot(&rho, &self.south), dot(&rho, &self.east), dot(&rho, &self.zenith));
+        Some(Look {
+            time_ms,
+            azimuth_deg: e.atan2(-s).to_degrees().rem_euclid(360.0),
+            elevation_deg: (z / range_km).asin().to_degrees(),
+            range_km,
+            range_rate_km_s: dot(&rho, &v_ecef) / range_km,
+            illumination,
+        })
+    }
+}
+
+#[derive(Serialize, Clone, Copy, Debug)]
+pub(crate) struct Look {
+    pub(crate) time_ms: f64,
+    pub(crate) azimuth_deg: f64,
+    pub(crate) elevation_deg: f64,
+    pub(crate) range_km: f64,
+    pub(crate) range_rate_km_s: f64,
+    pub(crate) illumination: Illumination,
+}
+
+#[derive(Serialize, Debug)]
+pub(crate) struct Pass {
+    pub(crate) aos: Look,
+    pub(crate) tca: Look,
+    pub(crate) los: Look,
+    pub(crate) visible: bool,
+}
+
+fn elevation(observer: &Observer, sat: &RealSat, t: f64) -> Option<f64> {
+    observer.look_at(sat, t).map(|l| l.elevation_deg)
+}
+
+/// Bisects the horizon crossing between `lo` (on the `lo_up` side) and
+/// `hi` (on the other).
+fn horizon_crossing(observer: &Observer, sat: &RealSat, mut lo: f64, mut hi: f64, lo_up: bool) -> Option<f64> {
+    while hi - lo > REFINE_MS {
+        let mid = 0.5 * (lo + hi);
+        if (elevation(observer, sat, mid)? > 0.0) == lo_up {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Some(0.5 * (lo + hi))
+}
+
+/// Highest point between AOS and LOS, by golden-section search.
+fn culmination(observer: &Observer, sat: &RealSat, mut lo: f64, mut hi: f64) -> Option<f64> {
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let mut a = hi - ratio * (hi - lo);
+    let mut b = lo + ratio * (hi - lo);
+    let (mut ea, mut eb) = (elevation(observer, sat, a)?, elevation(observer, sat, b)?);
+    while hi - lo > REFINE_MS {
+        if ea > eb {
+            hi = b;
+            (b, eb) = (a, ea);
+            a = hi - ratio * (hi - lo);
+            ea = elevation(observer, sat, a)?;
+        } else {
+            lo = a;
+            (a, ea) = (b, eb);
+            b = lo + ratio * (hi - lo);
+            eb = elevation(observer, sat, b)?;
+        }
+    }
+    Some(0.5 * (lo + hi))
+}
+
+fn visible(observer: &Observer, sat: &RealSat, aos_ms: f64, los_ms: f64) -> bool {
+    let steps = ((los_ms - aos_ms) / VISIBILITY_STEP_MS).ceil() as usize;
+    (0..=steps).any(|k| {
+        let t = (aos_ms + k as f64 * VISIBILITY_STEP_MS).min(los_ms);
+        observer.in_darkness(t) && observer.look_at(sat, t).is_some_and(|l| l.illumination == Illumination::Sunlit)
+    })
+}
+
+/// Complete passes of `sat` over `observer` within `[start_ms, end_ms]`.
+pub(crate) fn predict_passes(observer: &Observer, sat: &RealSat, start_ms: f64, end_ms: f64) -> Vec<Pass> {
+    let mut passes = Vec::new();
+    let Some(mut prev_up) = elevation(observer, sat, start_ms).map(|e| e > 0.0) else { return passes };
+    let mut prev_t = start_ms;
+    let mut aos = None;
+    while prev_t < end_ms {
+        let t = (prev_t + SCAN_STEP_MS).min(end_ms);
+        let Some(up) = elevation(observer, sat, t).map(|e| e > 0.0) else { break };
+        if up != prev_up {
+            let Some(crossing) = horizon_crossing(observer, sat, prev_t, t, prev_up) else { break };
+            if up {
+                aos = Some(crossing);
+            } else if let Some(rise) = aos.take() {
+                let pass = culmination(observer, sat, rise, crossing).and_then(|peak| {
+                    Some(Pass {
+                        aos: observer.look_at(sat, rise)?,
+                        tca: observer.look_at(sat, peak)?,
+                        los: observer.look_at(sat, crossing)?,
+                        visible: visible(observer, sat, rise, crossing),
+                    })
+                });
+                passes.extend(pass);
+            }
+        }
+        (prev_t, prev_up) = (t, up);
+    }
+    passes
+}
+
+#[derive(Deserialize)]
+pub struct PassesQuery {
+    lat: f64,
+    lon: f64,
+    /// Metres above the ellipsoid.
+    #[serde(default)]
+    alt: f64,
+    days: Option<f64>,
+}
+
+pub async fn get_passes(
+    State(state): State<SatellitesAppState>,
+    Path(norad_id): Path<u32>,
+    Query(query): Query<PassesQuery>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let days = query.days.unwrap_or(2.0);
+    if !(-90.0..=90.0).contains(&query.lat) || !(-180.0..=360.0).contains(&query.lon) || !query.alt.is_finite() {
+        return Err((StatusCode::BAD_REQUEST, "lat must be within ±90°, lon within -180..360°".to_string()));
+    }
+    if !(days > 0.0 && days <= MAX_DAYS) {
+        return Err((StatusCode::BAD_REQUEST, format!("days must be in (0, {MAX_DAYS}]")));
+    }
+    let cache = state.runtime.catalog().await.ok_or((StatusCode::SERVICE_UNAVAILABLE, "no TLEs loaded yet".to_string()))?;
+    if cache.sat(norad_id).is_none() {
+        return Err((StatusCode::NOT_FOUND, format!("NORAD {norad_id} is not in the tracked catalog")));
+    }
+
+    let observer = Observer::new(query.lat, query.lon, query.alt / 1000.0);
+    let start_ms = now_ms();
+    let end_ms = start_ms + days * 86_400_000.0;
+    let passes = tokio::task::spawn_blocking(move || {
+        cache.sat(norad_id).map(|sat| predict_passes(&observer, sat, start_ms, end_ms)).unwrap_or_default()
+    })
+    .await
+    .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, String::new()))?;
+
+    Ok(Json(json!({
+        "norad_id": norad_id,
+        "start_unix_ms": start_ms,
+        "end_unix_ms": end_ms,
+        "passes": passes,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passes_rise_culminate_and_set_in_order() {
+        let iss = RealSat::from_tle(
+            "ISS (ZARYA)",
+            "1 25544U 98067A   24001.50000000  .00016717  00000-0  10270-3 0  9009",
+            "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537",
+        )
+        .unwrap();
+        let observer = Observer::new(40.0, -75.0, 0.0);
+        let epoch_ms = iss.epoch_unix_ms();
+        let passes = predict_passes(&observer, &iss, epoch_ms, epoch_ms + 86_400_000.0);
+        // A 51.6° orbit passes over 40°N several times a day.
+        assert!(passes.len() >= 3, "{passes:?}");
+        for p in &passes {
+            assert!(p.aos.time_ms < p.tca.time_ms && p.tca.time_ms < p.los.time_ms);
+            assert!(p.aos.elevation_deg.abs() < 0.1 && p.los.elevation_deg.abs() < 0.1);
+            assert!(p.tca.elevation_deg > 0.0 && p.tca.range_km < p.aos.range_km);
+            // Approaching at rise, receding at set, stationary at the top.
+            assert!(p.aos.range_rate_km_s < 0.0 && p.los.range_rate_km_s > 0.0);
+            assert!(p.tca.range_rate_km_s.abs() < 1.0);
+            assert!(p.los.time_ms - p.aos.time_ms < 15.0 * 60_000.0);
+            // Never visible with the Sun up.
+            if !observer.in_darkness(p.aos.time_ms) && !observer.in_darkness(p.los.time_ms) {
+                assert!(!p.visible);
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! `GET /api/satellites/stream` — the loop's snapshots pushed as they're
+//! made, instead of every viewer polling `/api/satellites` on its own timer
+//! and landing on whichever tick happens to be current. The background
+//! loop publishes each snapshot on `SatellitesRuntime.ticks`, a broadcast
+//! channel holding only the newest; a subscriber that can't keep up is told
+//! it lagged and carries on from that newest one, so a slow connection
+//! drops ticks rather than falling further and further behind.
+//!
+//! Each event's SSE `id` is the tick's sequence number. The data is the
+//! JSON snapshot by default, or with `?format=binary` the packed snapshot
+//! of `satellites_wire.rs`, base64-encoded since SSE carries text (still
+//! well under a tenth of the JSON). Both are encoded once per tick, the
+//! binary by the loop and the JSON by whichever request or subscriber
+//! wants it first (see `Snapshot`). The current snapshot is sent on connect
+//! so a new viewer doesn't wait a tick for its first positions.
+//!
+//! Sequence numbers are per replica: a reconnect that lands on another pod
+//! starts from that pod's count.
+
+use crate::satellites::{SatellitesAppState, Snapshot};
+use axum::extract::{Query, State};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use serde::Deserialize;
+use std::sync::Arc;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum Format {
+    #[default]
+    Json,
+    Binary,
+}
+
+#[derive(Deserialize)]
+pub struct StreamQuery {
+    #[serde(default)]
+    format: Format,
+}
+
+/// Copies data encoded once per tick; nothing is serialized per
+/// subscriber.
+async fn event(snapshot: &Arc<Snapshot>, format: Format) -> Event {
+    let data = match format {
+        Format::Json => snapshot.json_text().await,
+        Format::Binary => snapshot.binary_base64.clone(),
+    };
+    Event::default().id(snapshot.seq.to_string()).data(&*data)
+}
+
+/// The next snapshot to send: the current one first, then each broadcast
+/// one, skipping to the newest after a lag. `None` once the loop is gone.
+async fn next_snapshot(first: &mut Option<Arc<Snapshot>>, rx: &mut broadcast::Receiver<Arc<Snapshot>>) -> Option<Arc<Snapshot>> {
+    if let Some(snapshot) = first.take() {
+        return Some(snapshot);
+    }
+    loop {
+        match rx.recv().await {
+            Ok(snapshot) => return Some(snapshot),
+            Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => return None,
+        }
+    }
+}
+
+pub async fn snapshot_stream(
+    State(state): State<SatellitesAppState>,
+    Query(query): Query<StreamQuery>,
+) -> Sse<impl futures_util::Stream<Item = Result<Event, std::convert::Infallible>>> {
+    // Subscribe before reading the current snapshot so no tick falls
+    // between the two.
+    let rx = state.runtime.ticks.subscribe();
+    let first = Some(state.runtime.snapshot.read().await.clone());
+    let format = query.format;
+    // State: (snapshot not yet sent, receiver, last sequence sent).
+    let stream = futures_util::stream::unfold((first, rx, None), move |(mut first, mut rx, last_seq)| async move {
+        loop {
+            let snapshot = next_snapshot(&mut first, &mut rx).await?;
+            // The tick just read as current may also arrive by broadcast.
+            if last_seq < Some(snapshot.seq) {
+                return Some((Ok(event(&snapshot, format).await), (first, rx, Some(snapshot.seq))));
+            }
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::new().interval(std::time::Duration::from_secs(15)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(seq: u64) -> Arc<Snapshot> {
+        Arc::new(Snapshot::empty(seq))
+    }
+
+    #[tokio::test]
+    async fn a_lagging_subscriber_skips_to_the_newest_snapshot() {
+        let (tx, mut rx) = broadcast::channel(1);
+        let mut first = Some(snapshot(1));
+        for seq in 2..=5 {
+            assert!(tx.send(snapshot(seq)).is_ok());
+        }
+        assert_eq!(next_snapshot(&mut first, &mut rx).await.unwrap().seq, 1);
+        assert_eq!(next_snapshot(&mut first, &mut rx).await.unwrap().seq, 5);
+        drop(tx);
+        assert!(next_snapshot(&mut first, &mut rx).await.is_none());
+    }
+}
@@ -0,0 +1,113 @@
+//! `application/octet-stream` variant of `/api/satellites`, picked by the
+//! request's `Accept`. The JSON snapshot spells out ~16k objects with their
+//! field names every tick — several megabytes a second per viewer — when
+//! all that changes between ticks is where each one is and whether it's in
+//! shadow. The binary snapshot carries only that, packed:
+//!
+//! ```text
+//! u32  catalog version        (all little-endian)
+//! u32  count
+//! f64  time_ms
+//! f32  x, y, z  × count       render frame, NaN where SGP4 failed
+//! u8   illumination × count   0 sunlit, 1 penumbra, 2 umbra, 255 none
+//! ```
+//!
+//! The header is 16 bytes so the coordinates can be read as a
+//! `Float32Array` in place. Entries are in catalog order and line up with
+//! the ID table at `/api/satellites/ids`, which only changes when the TLEs
+//! are refreshed:
+//!
+//! ```text
+//! u32  catalog version
+//! u32  count
+//! u32  norad_id × count
+//! f32  inclination_deg × count
+//! ```
+//!
+//! The version is the catalog's `fetched_at` in unix seconds, so every
+//! replica serving the same Postgres-cached catalog agrees on it, and a
+//! client only refetches the table when the snapshot's version differs
+//! from the one it holds. Both are encoded once — the snapshot by the
+//! background loop each tick, the table when a catalog is loaded — and
+//! served as shared `Bytes`.
+
+use crate::satellites::RealSat;
+use crate::satellites_eclipse::Illumination;
+use axum::body::Bytes;
+use axum::http::{header, HeaderMap};
+
+pub(crate) const OCTET_STREAM: &str = "application/octet-stream";
+const HEADER_LEN: usize = 16;
+
+/// Whether the request's `Accept` asks for the binary snapshot.
+pub(crate) fn wants_binary(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| accept.contains(OCTET_STREAM))
+}
+
+fn illumination_code(illumination: Option<Illumination>) -> u8 {
+    match illumination {
+        Some(Illumination::Sunlit) => 0,
+        Some(Illumination::Penumbra) => 1,
+        Some(Illumination::Umbra) => 2,
+        None => 255,
+    }
+}
+
+/// One tick's positions (render frame, Earth radii) and illumination, one
+/// entry per catalog object.
+pub(crate) fn encode_positions(version: u32, time_ms: f64, states: &[Option<([f64; 3], Illumination)>]) -> Bytes {
+    let mut out = Vec::with_capacity(HEADER_LEN + states.len() * 13);
+    out.extend_from_slice(&version.to_le_bytes());
+    out.extend_from_slice(&(states.len() as u32).to_le_bytes());
+    out.extend_from_slice(&time_ms.to_le_bytes());
+    for state in states {
+        let xyz = state.map_or([f64::NAN; 3], |(p, _)| p);
+        for c in xyz {
+            out.extend_from_slice(&(c as f32).to_le_bytes());
+        }
+    }
+    out.extend(states.iter().map(|s| illumination_code(s.map(|(_, i)| i))));
+    Bytes::from(out)
+}
+
+/// The catalog's NORAD IDs and inclinations, in the order the position
+/// snapshots use.
+pub(crate) fn encode_ids(version: u32, sats: &[RealSat]) -> Bytes {
+    let mut out = Vec::with_capacity(8 + sats.len() * 8);
+    out.extend_from_slice(&version.to_le_bytes());
+    out.extend_from_slice(&(sats.len() as u32).to_le_bytes());
+    for sat in sats {
+        out.extend_from_slice(&sat.norad_id.to_le_bytes());
+    }
+    for sat in sats {
+        out.extend_from_slice(&(sat.inclination_deg as f32).to_le_bytes());
+    }
+    Bytes::from(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_pack_after_an_aligned_header() {
+        let states = [Some(([1.0, -2.0, 0.5], Illumination::Umbra)), None];
+        let bytes = encode_positions(7, 1_700_000_000_000.0, &states);
+        assert_eq!(bytes.len(), HEADER_LEN + 2 * 12 + 2);
+        assert_eq!(u32::from_le_bytes(bytes[0..4].try_into().unwrap()), 7);
+        assert_eq!(u32::from_le_bytes(bytes[4..8].try_into().unwrap()), 2);
+        assert_eq!(f64::from_le_bytes(bytes[8..16].try_into().unwrap()), 1_700_000_000_000.0);
+        let f = |i: usize| f32::from_le_bytes(bytes[HEADER_LEN + 4 * i..HEADER_LEN + 4 * i + 4].try_into().unwrap());
+        assert_eq!([f(0), f(1), f(2)], [1.0, -2.0, 0.5]);
+        assert!(f(3).is_nan() && f(4).is_nan() && f(5).is_nan());
+        assert_eq!(&bytes[HEADER_LEN + 24..], &[2, 255]);
+
+        let mut headers = HeaderMap::new();
+        assert!(!wants_binary(&headers));
+        headers.insert(header::ACCEPT, "application/octet-stream, */*;q=0.1".parse().unwrap());
+        assert!(wants_binary(&headers));
+    }
+}
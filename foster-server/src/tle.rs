@@ -0,0 +1,355 @@
+//! Fetching TLEs from CelesTrak without mistaking a failure for an empty
+//! sky. CelesTrak answers plenty of requests with something other than
+//! element sets — an HTTP error, or a 403 with a plain-text notice when the
+//! same group is re-downloaded before it has changed — and reading any of
+//! those as "no TLEs" used to let a screening record `complete` with zero
+//! pairs. Every way a fetch can come back empty-handed is a `TleError`
+//! here, and callers decide what that means for them.
+//!
+//! Element sets with a bad line checksum are dropped (one corrupt line
+//! shouldn't sink a 16k-object group) and counted in `TleSet::bad_checksums`;
+//! a response where every set fails is an error of its own.
+//!
+//! On top of that sits the one TLE catalog both conjunction screening and
+//! satellite tracking read from (`get_catalog`): every `TleSource` — a
+//! CelesTrak group or a single catalog number — is cached as a row of
+//! `tle_cache` (migration 0010) and served from there until it's older than
+//! `CATALOG_TTL`. A stale row is refreshed by whichever replica wins that
+//! source's minute bucket in `tle_refresh_claims` (migration 0022 keyed it
+//! per source), the same race `satellites.rs` used to run for `active`
+//! alone; everyone else keeps serving the stale row meanwhile, so nothing
+//! waits on CelesTrak unless nothing is cached at all, and CelesTrak sees
+//! one download per source per TTL rather than one per screening.
+//!
+//! `TLE_FIXTURE_DIR` (a directory of `<cache key>.tle` files, e.g.
+//! `active.tle`, `catnr-25544.tle`) and, for `active` only, the older
+//! `SATELLITES_TLE_FIXTURE` stand in for CelesTrak in local development,
+//! where repeat downloads of an unchanged group trip its throttle.
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::PgPool;
+use std::time::Duration;
+
+const USER_AGENT: &str = "Mozilla/5.0 (compatible; jaydanhoward-foster-migration)";
+/// CelesTrak's wording when it refuses to re-serve unchanged data.
+const THROTTLE_NOTICE: &str = "has not updated since your last successful download";
+/// Same TTL the satellites module always used; CelesTrak itself only
+/// republishes every couple of hours.
+pub const CATALOG_TTL: Duration = Duration::from_secs(6 * 3600);
+/// `active` (~16k objects) has been seen to take ~44s.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(90);
+/// How long a replica with nothing cached waits for another replica's
+/// refresh of the same source to land.
+const REFRESH_WAIT: Duration = Duration::from_secs(90);
+const REFRESH_POLL: Duration = Duration::from_secs(3);
+
+#[derive(thiserror::Error, Debug)]
+pub enum TleError {
+    #[error("CelesTrak request for {query} failed: {message}")]
+    Network { query: String, message: String },
+    #[error("CelesTrak returned HTTP {status} for {query}: {body}")]
+    HttpStatus { query: String, status: u16, body: String },
+    #[error("CelesTrak is throttling {query}: {notice}")]
+    Throttled { query: String, notice: String },
+    #[error("CelesTrak returned no element sets for {query}")]
+    NoObjects { query: String },
+    #[error("all {count} element sets for {query} failed their checksum")]
+    Checksum { query: String, count: usize },
+    #[error("fixture {path} unreadable: {message}")]
+    Fixture { path: String, message: String },
+    #[error("nothing cached for {query} and another replica's refresh didn't land")]
+    RefreshPending { query: String },
+}
+
+/// Name, line 1, line 2.
+pub type Tle = (String, String, String);
+
+/// The element sets from one response, with the number dropped for a bad
+/// checksum.
+pub struct TleSet {
+    pub tles: Vec<Tle>,
+    pub bad_checksums: usize,
+}
+
+/// Modulo-10 checksum of a line's first 68 columns: digits count their
+/// value, `-` counts 1.
+pub(crate) fn line_checksum(body: &str) -> u32 {
+    body.chars()
+        .map(|c| match c {
+            '-' => 1,
+            c => c.to_digit(10).unwrap_or(0),
+        })
+        .sum::<u32>()
+        % 10
+}
+
+/// Whether column 69 holds `line_checksum` of the rest.
+fn checksum_ok(line: &str) -> bool {
+    let Some((body, check)) = line.get(..68).zip(line.get(68..69)) else { return false };
+    check.parse::<u32>().is_ok_and(|c| c == line_checksum(body))
+}
+
+/// Three-line sets as CelesTrak serves them (two-line sets get an empty
+/// name). `query` only labels errors.
+pub fn parse_tles(query: &str, body: &str) -> Result<TleSet, TleError> {
+    let lines: Vec<&str> = body.lines().map(str::trim_end).filter(|l| !l.is_empty()).collect();
+    let mut tles = Vec::new();
+    let mut bad_checksums = 0;
+    let mut i = 0;
+    while i + 1 < lines.len() {
+        let (line1, line2) = (lines[i], lines[i + 1]);
+        if !(line1.starts_with("1 ") && line2.starts_with("2 ")) {
+            i += 1;
+            continue;
+        }
+        let name = match i.checked_sub(1).map(|p| lines[p]) {
+            Some(prev) if !prev.starts_with("2 ") => prev.trim().to_string(),
+            _ => String::new(),
+        };
+        if checksum_ok(line1) && checksum_ok(line2) {
+            tles.push((name, line1.to_string(), line2.to_string()));
+        } else {
+            bad_checksums += 1;
+        }
+        i += 2;
+    }
+
+    if !tles.is_empty() {
+        return Ok(TleSet { tles, bad_checksums });
+    }
+    if bad_checksums > 0 {
+        return Err(TleError::Checksum { query: query.to_string(), count: bad_checksums });
+    }
+    if body.contains(THROTTLE_NOTICE) {
+        return Err(TleError::Throttled { query: query.to_string(), notice: body.trim().to_string() });
+    }
+    Err(TleError::NoObjects { query: query.to_string() })
+}
+
+/// One CelesTrak GP query (`GROUP=...` or `CATNR=...`).
+pub fn fetch_tles_blocking(query: &str, timeout: Duration) -> Result<TleSet, TleError> {
+    let network = |e: reqwest::Error| TleError::Network { query: query.to_string(), message: e.to_string() };
+    let url = format!("https://celestrak.org/NORAD/elements/gp.php?{query}&FORMAT=tle");
+    let response = reqwest::blocking::Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(timeout)
+        .build()
+        .and_then(|c| c.get(&url).send())
+        .map_err(network)?;
+    let status = response.status();
+    let body = response.text().map_err(network)?;
+    if body.contains(THROTTLE_NOTICE) {
+        return Err(TleError::Throttled { query: query.to_string(), notice: body.trim().to_string() });
+    }
+    if !status.is_success() {
+        let body: String = body.trim().chars().take(200).collect();
+        return Err(TleError::HttpStatus { query: query.to_string(), status: status.as_u16(), body });
+    }
+    parse_tles(query, &body)
+}
+
+/// Where a set of element sets comes from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TleSource {
+    Group(String),
+    Catnr(u32),
+}
+
+impl TleSource {
+    pub fn group(name: &str) -> Self {
+        Self::Group(name.to_string())
+    }
+
+    /// `tle_cache.group_name`, and the fixture file stem.
+    pub fn cache_key(&self) -> String {
+        match self {
+            Self::Group(name) => name.clone(),
+            Self::Catnr(id) => format!("catnr-{id}"),
+        }
+    }
+
+    /// The CelesTrak GP query.
+    pub fn query(&self) -> String {
+        match self {
+            Self::Group(name) => format!("GROUP={name}"),
+            Self::Catnr(id) => format!("CATNR={id}"),
+        }
+    }
+
+    fn fixture_path(&self) -> Option<String> {
+        if let Ok(dir) = std::env::var("TLE_FIXTURE_DIR") {
+            let path = std::path::Path::new(&dir).join(format!("{}.tle", self.cache_key()));
+            if path.exists() {
+                return Some(path.to_string_lossy().into_owned());
+            }
+        }
+        match self {
+            Self::Group(name) if name == "active" => std::env::var("SATELLITES_TLE_FIXTURE").ok(),
+            _ => None,
+        }
+    }
+}
+
+/// A source's element sets as cached, and when they were actually fetched
+/// from CelesTrak (not when this process read them).
+pub struct Catalog {
+    pub tles: Vec<Tle>,
+    pub fetched_at: DateTime<Utc>,
+    /// Sets dropped for bad checksums on the fetch that produced this.
+    pub bad_checksums: usize,
+}
+
+impl Catalog {
+    pub fn is_stale(&self) -> bool {
+        Utc::now().signed_duration_since(self.fetched_at).to_std().unwrap_or(CATALOG_TTL) >= CATALOG_TTL
+    }
+}
+
+/// The fixture if one is configured for `source`, otherwise CelesTrak.
+fn fetch_source_blocking(source: &TleSource) -> Result<TleSet, TleError> {
+    match source.fixture_path() {
+        Some(path) => {
+            let body = std::fs::read_to_string(&path).map_err(|e| TleError::Fixture { path, message: e.to_string() })?;
+            parse_tles(&source.query(), &body)
+        }
+        None => fetch_tles_blocking(&source.query(), FETCH_TIMEOUT),
+    }
+}
+
+/// Rows written before names were kept hold bare line pairs; they read
+/// back with empty names.
+fn tles_from_json(value: Value) -> Option<Vec<Tle>> {
+    match serde_json::from_value::<Vec<Tle>>(value.clone()) {
+        Ok(tles) => Some(tles),
+        Err(_) => {
+            let pairs: Vec<(String, String)> = serde_json::from_value(value).ok()?;
+            Some(pairs.into_iter().map(|(l1, l2)| (String::new(), l1, l2)).collect())
+        }
+    }
+}
+
+/// Whatever `tle_cache` holds for `source`, however old.
+pub async fn load_cached(pool: &PgPool, source: &TleSource) -> Option<Catalog> {
+    let row: (Value, DateTime<Utc>) = sqlx::query_as("SELECT satellites, fetched_at FROM tle_cache WHERE group_name = $1")
+        .bind(source.cache_key())
+        .fetch_optional(pool)
+        .await
+        .ok()
+        .flatten()?;
+    Some(Catalog { tles: tles_from_json(row.0)?, fetched_at: row.1, bad_checksums: 0 })
+}
+
+async fn save_cached(pool: &PgPool, source: &TleSource, catalog: &Catalog) {
+    let json = serde_json::to_value(&catalog.tles).unwrap_or(Value::Null);
+    let _ = sqlx::query(
+        "INSERT INTO tle_cache (group_name, satellites, fetched_at) VALUES ($1, $2, $3)
+         ON CONFLICT (group_name) DO UPDATE SET satellites = EXCLUDED.satellites, fetched_at = EXCLUDED.fetched_at",
+    )
+    .bind(source.cache_key())
+    .bind(json)
+    .bind(catalog.fetched_at)
+    .execute(pool)
+    .await;
+}
+
+/// Try to atomically claim the right to refetch `source` for this minute
+/// bucket. Returns true if this replica won the race — same INSERT ON
+/// CONFLICT DO NOTHING pattern as spike_claims/cluster_audit_claims.
+async fn try_claim_refresh(pool: &PgPool, source: &TleSource) -> bool {
+    let result = sqlx::query(
+        "INSERT INTO tle_refresh_claims (group_name, bucket) \
+         VALUES ($1, date_trunc('minute', NOW())) \
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(source.cache_key())
+    .execute(pool)
+    .await;
+
+    matches!(result, Ok(r) if r.rows_affected() == 1)
+}
+
+async fn fetch_and_cache(pool: Option<&PgPool>, source: &TleSource) -> Result<Catalog, TleError> {
+    let to_fetch = source.clone();
+    let set = tokio::task::spawn_blocking(move || fetch_source_blocking(&to_fetch))
+        .await
+        .map_err(|e| TleError::Network { query: source.query(), message: e.to_string() })??;
+    let catalog = Catalog { tles: set.tles, fetched_at: Utc::now(), bad_checksums: set.bad_checksums };
+    if let Some(pool) = pool {
+        save_cached(pool, source, &catalog).await;
+    }
+    Ok(catalog)
+}
+
+/// `source`'s element sets: the cached row while it's fresh; otherwise a
+/// refetch if this replica wins the refresh claim, falling back to the
+/// stale row if the refetch fails. A replica that loses the claim serves
+/// the stale row, or with nothing cached waits for the winner's. Without
+/// a pool (local dev) it's a plain fetch.
+pub async fn get_catalog(pool: Option<&PgPool>, source: &TleSource) -> Result<Catalog, TleError> {
+    let Some(pool) = pool else { return fetch_and_cache(None, source).await };
+    let cached = match load_cached(pool, source).await {
+        Some(catalog) if !catalog.is_stale() => return Ok(catalog),
+        cached => cached,
+    };
+
+    if try_claim_refresh(pool, source).await {
+        return match (fetch_and_cache(Some(pool), source).await, cached) {
+            (Ok(fresh), _) => Ok(fresh),
+            (Err(e), Some(stale)) => {
+                eprintln!("TLE refresh failed, serving cache from {}: {e}", stale.fetched_at);
+                Ok(stale)
+            }
+            (Err(e), None) => Err(e),
+        };
+    }
+    if let Some(stale) = cached {
+        return Ok(stale);
+    }
+    let deadline = tokio::time::Instant::now() + REFRESH_WAIT;
+    while tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(REFRESH_POLL).await;
+        if let Some(catalog) = load_cached(pool, source).await {
+            return Ok(catalog);
+        }
+    }
+    Err(TleError::RefreshPending { query: source.query() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISS: &str = "ISS (ZARYA)
+1 25544U 98067A   24001.50000000  .00016717  00000-0  10270-3 0  9009
+2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537
+";
+
+    #[test]
+    fn parses_sets_and_drops_bad_checksums() {
+        let corrupt = ISS.replace("ISS (ZARYA)", "CORRUPT").replace("9009", "9008");
+        let set = parse_tles("GROUP=stations", &format!("{ISS}{corrupt}")).unwrap();
+        assert_eq!(set.tles.len(), 1);
+        assert_eq!(set.tles[0].0, "ISS (ZARYA)");
+        assert_eq!(set.bad_checksums, 1);
+
+        assert!(matches!(parse_tles("GROUP=stations", &corrupt), Err(TleError::Checksum { count: 1, .. })));
+    }
+
+    #[test]
+    fn reads_cache_rows_with_and_without_names() {
+        let named = serde_json::json!([["ISS (ZARYA)", "1 25544U", "2 25544"]]);
+        assert_eq!(tles_from_json(named).unwrap()[0].0, "ISS (ZARYA)");
+        let bare = serde_json::json!([["1 25544U", "2 25544"]]);
+        let tles = tles_from_json(bare).unwrap();
+        assert_eq!((tles[0].0.as_str(), tles[0].1.as_str()), ("", "1 25544U"));
+        assert_eq!(TleSource::Catnr(25544).cache_key(), "catnr-25544");
+    }
+
+    #[test]
+    fn tells_a_throttle_notice_from_an_empty_group() {
+        let notice = "GP data has not updated since your last successful download of GROUP=active at 2024-01-01 12:00:00 UTC.";
+        assert!(matches!(parse_tles("GROUP=active", notice), Err(TleError::Throttled { .. })));
+        assert!(matches!(parse_tles("GROUP=nope", "Invalid query"), Err(TleError::NoObjects { .. })));
+    }
+}
@@ -2,7 +2,11 @@
 // (GET /api/conjunction) independently of Foster's SSE for the
 // "conjunction" machine, which only tracks the button's idle/started
 // label. The actual screening pass (Hoots filter + SGP4 + TCA, real TLE
-// data) is a background job; see src/conjunction.rs.
+// data) is a background job split across replicas, with its state in
+// Postgres; see src/conjunction.rs. A start POST answered 409 means one
+// is already running, which polling picks up the same way. While one runs,
+// its progress comes from the SSE stream at
+// /api/conjunction/screenings/:id/progress (any replica can serve it).
 
 export function initConjunction() {
   const button = document.getElementById('conjunction-start');
@@ -12,24 +16,90 @@ export function initConjunction() {
   if (!button || !statusEl || !statsEl || !eventsEl) return;
 
   let polling = null;
+  let progress = null;
+
+  function formatEta(s) {
+    if (s == null) return '…';
+    return s < 90 ? `${Math.round(s)}s` : `${Math.round(s / 60)}min`;
+  }
+
+  function watchProgress(id) {
+    if (progress) return;
+    if (polling) { clearInterval(polling); polling = null; }
+    progress = new EventSource(`/api/conjunction/screenings/${id}/progress`);
+    progress.onmessage = (msg) => {
+      const p = JSON.parse(msg.data);
+      if (p.status === 'running') {
+        statsEl.textContent = `${p.units_done}/${p.units_total} ${p.unit}s, ${p.pairs_after_hoots} pairs past Hoots, ${p.events_found} events so far — ${(p.fraction * 100).toFixed(0)}%, ETA ${formatEta(p.eta_s)}`;
+      } else {
+        progress.close();
+        progress = null;
+        poll();
+      }
+    };
+    // Stream dropped (replica restart): fall back to polling, which
+    // reopens it if the screening is still running.
+    progress.onerror = () => {
+      progress.close();
+      progress = null;
+      if (!polling) polling = setInterval(poll, 3000);
+    };
+  }
 
   async function poll() {
-    const res = await fetch('/api/conjunction');
+    // Most probable collisions first — raw miss distance alone ranks a
+    // tight pass between two well-tracked payloads above a slightly wider
+    // one through a debris cloud's much larger uncertainty.
+    const res = await fetch('/api/conjunction?sort=pc');
     const data = await res.json();
     statusEl.textContent = data.status;
 
-    if (data.status === 'complete') {
-      statsEl.textContent = `${data.events_found} events found across ${data.pairs_after_hoots} pairs (of ${data.total_pairs} total) in ${data.elapsed_ms}ms`;
+    // The screening may have been started by another visitor (or another
+    // replica) — follow its progress until it settles either way.
+    if (data.status === 'running') {
+      watchProgress(data.id);
+    } else if (data.status === 'complete') {
+      const r = data.hoots_rejections;
+      const filtered = r ? ` — Hoots rejected ${r.altitude} by altitude, ${r.orbit_path} by orbit path, ${r.time} by timing` : '';
+      statsEl.textContent = `${data.events_found} events found across ${data.pairs_after_hoots} pairs (of ${data.total_pairs} total) in ${data.elapsed_ms}ms${filtered}`;
+      // Partial TLE fetches still screen; say which sources are missing.
+      if (data.fetch_warnings && data.fetch_warnings.length) {
+        statsEl.textContent += ` — incomplete catalog: ${data.fetch_warnings.join('; ')}`;
+      }
+      if (data.total > data.events.length) {
+        statsEl.textContent += ` — showing the top ${data.events.length} of ${data.total}`;
+      }
       eventsEl.innerHTML = data.events
-        .map((e) => `<li>${e.sat_a} vs ${e.sat_b} — ${e.miss_distance_km.toFixed(1)} km</li>`)
+        .map((e) => `<li>${e.sat_a} vs ${e.sat_b} — ${e.miss_distance_km.toFixed(1)} km${e.miss_radial_km == null ? '' : ` (radial ${e.miss_radial_km.toFixed(2)} km)`}, Pc ${e.collision_probability.toExponential(1)} <a href="/api/conjunction/events/${e.id}/cdm">CDM</a>${e.sat_a_norad_id == null ? '' : ` <a href="?event=${e.id}#satellites">show</a>`} <a href="#" data-whatif="${e.id}" data-tca="${e.tca_unix_ms}">what-if</a><div class="whatif"></div></li>`)
         .join('');
       if (polling) { clearInterval(polling); polling = null; }
-    } else if (data.status === 'failed') {
-      statsEl.textContent = data.error || 'failed';
+    } else if (data.status === 'failed' || data.status === 'cancelled') {
+      statsEl.textContent = data.error || data.status;
       if (polling) { clearInterval(polling); polling = null; }
     }
   }
 
+  // Avoidance what-if (src/conjunction_maneuver.rs): a 0.1 m/s along-track
+  // burn by the first object six hours out, and the Δv that would bring Pc
+  // lowest within the ±0.2 m/s sweep.
+  eventsEl.addEventListener('click', async (ev) => {
+    const link = ev.target.closest('[data-whatif]');
+    if (!link) return;
+    ev.preventDefault();
+    const out = link.nextElementSibling;
+    const burn = Number(link.dataset.tca) - 6 * 3600 * 1000;
+    out.textContent = 'analysing…';
+    const res = await fetch(`/api/conjunction/events/${link.dataset.whatif}/maneuver?burn_unix_ms=${burn}&delta_v_m_s=0.1`);
+    if (!res.ok) { out.textContent = await res.text() || `what-if unavailable (${res.status})`; return; }
+    const data = await res.json();
+    const m = data.maneuver;
+    const best = data.curve.reduce((a, b) => (b.collision_probability < a.collision_probability ? b : a), data.curve[0]);
+    out.textContent = m
+      ? `+0.1 m/s 6h before: miss ${m.miss_distance_km.toFixed(2)} km, Pc ${m.collision_probability.toExponential(1)}` +
+        (best ? `; lowest Pc ${best.collision_probability.toExponential(1)} at ${best.delta_v_m_s.toFixed(2)} m/s` : '')
+      : 'no solution';
+  });
+
   button.addEventListener('click', async () => {
     statsEl.textContent = '';
     eventsEl.innerHTML = '';
@@ -5,11 +5,11 @@
 // pin). The one thing NOT ported line-for-line: the real site re-runs
 // sgp4 in the browser every animation frame; Foster has no custom WASM,
 // so src/satellites.rs runs the same sgp4 crate server-side once per
-// tick (shared by every visitor) and this file polls /api/satellites for
-// the latest real snapshot, interpolating between the two most recent
-// ones for smooth motion. Foster itself only owns the run/pause +
-// playback-speed labels (fx-machine="satellites"); it never sees a
-// single satellite position.
+// tick (shared by every visitor) and pushes each real snapshot to this
+// file over /api/satellites/stream, which interpolates between the two
+// most recent ones for smooth motion. Foster itself only owns the
+// run/pause, replay/live mode and playback-speed labels
+// (fx-machine="satellites"); it never sees a single satellite position.
 
 const VERTEX_SHADER_SOURCE = `#version 300 es
 in vec3 position;
@@ -40,7 +40,12 @@ void main() {
 `;
 
 const ASTRANIS_IDS = new Set([56371, 62454, 62455, 62456, 62457]);
-const POLL_MS = 1000;
+// The two objects of the conjunction event named by the page's ?event=
+// (linked from the conjunction list), pinned like Astranis in their own
+// colour. Filled from the snapshot's `highlight`.
+const HIGHLIGHT_EVENT = new URLSearchParams(location.search).get('event');
+const highlightIds = new Set();
+const TICK_MS = 1000;
 
 function compileShader(gl, type, source) {
   const shader = gl.createShader(type);
@@ -155,7 +160,8 @@ function multiplyMatrices(a, b) {
   return result;
 }
 
-function getAltitudeColor(altitudeKm, inclinationDeg, isAstranis) {
+function getAltitudeColor(altitudeKm, inclinationDeg, isAstranis, isHighlighted) {
+  if (isHighlighted) return [1.0, 1.0, 1.0];
   if (isAstranis) return [0.0, 0.86, 0.71];
   if (altitudeKm > 35000.0 && altitudeKm < 37000.0 && Math.abs(inclinationDeg) < 5.0) {
     return [1.0, 0.3, 0.3];
@@ -167,6 +173,14 @@ function getAltitudeColor(altitudeKm, inclinationDeg, isAstranis) {
   return [0.8, 0.6, 1.0];
 }
 
+// Objects in the Earth's shadow (src/satellites_eclipse.rs) are drawn
+// dimmer, the way they'd fade from view; pinned ones keep full colour.
+function dimForShadow(color, illumination, pinned) {
+  if (pinned || !illumination || illumination === 'sunlit') return color;
+  const k = illumination === 'umbra' ? 0.35 : 0.7;
+  return [color[0] * k, color[1] * k, color[2] * k];
+}
+
 function bandIndex(altitudeKm, inclinationDeg) {
   if (altitudeKm > 35000.0 && altitudeKm < 37000.0 && Math.abs(inclinationDeg) < 5.0) return 4;
   if (altitudeKm < 600.0) return 0;
@@ -299,9 +313,10 @@ class SatelliteRenderer {
 
     for (const pos of positions) {
       const isAstranis = ASTRANIS_IDS.has(pos.norad_id);
-      const color = getAltitudeColor(pos.altitude_km, pos.inclination_deg, isAstranis);
+      const isHighlighted = highlightIds.has(pos.norad_id);
+      const color = dimForShadow(getAltitudeColor(pos.altitude_km, pos.inclination_deg, isAstranis, isHighlighted), pos.illumination, isAstranis || isHighlighted);
       const entry = [pos.x, pos.y, pos.z, color[0], color[1], color[2]];
-      if (isAstranis) astranis.push(...entry);
+      if (isAstranis || isHighlighted) astranis.push(...entry);
       else regular.push(...entry);
     }
 
@@ -476,19 +491,75 @@ export function initSatellites() {
     });
   });
 
-  // Real data: poll the shared server-computed snapshot (src/satellites.rs)
-  // and interpolate between the two most recent real samples for smooth
-  // motion between polls, instead of recomputing sgp4 in the browser.
+  // Real data: the shared server-computed snapshot (src/satellites.rs),
+  // pushed once per server tick over /api/satellites/stream
+  // (src/satellites_stream.rs), interpolated between the two most recent
+  // real samples for smooth motion between ticks, instead of recomputing
+  // sgp4 in the browser.
   let prev = null;
   let curr = null;
   let prevAt = 0;
   let currAt = 0;
 
-  async function poll() {
+  // Packed snapshot (src/satellites_wire.rs): a 16-byte header, xyz as
+  // Float32 per catalog object, then one illumination byte each. IDs and
+  // inclinations come from a table fetched again only when the catalog
+  // version changes.
+  const ILLUMINATION = ['sunlit', 'penumbra', 'umbra'];
+  let ids = null;
+
+  async function fetchIds(version) {
+    const buf = await (await fetch('/api/satellites/ids')).arrayBuffer();
+    const view = new DataView(buf);
+    const count = view.getUint32(4, true);
+    ids = {
+      version: view.getUint32(0, true),
+      norad: new Uint32Array(buf.slice(8, 8 + 4 * count)),
+      inclination: new Float32Array(buf.slice(8 + 4 * count, 8 + 8 * count)),
+    };
+    if (ids.version !== version) ids = null;
+  }
+
+  async function decodeBinary(buf) {
+    const view = new DataView(buf);
+    const version = view.getUint32(0, true);
+    const count = view.getUint32(4, true);
+    if (!ids || ids.version !== version) await fetchIds(version);
+    if (!ids || ids.norad.length !== count) return null;
+    const xyz = new Float32Array(buf, 16, 3 * count);
+    const light = new Uint8Array(buf, 16 + 12 * count, count);
+    const positions = [];
+    for (let i = 0; i < count; i++) {
+      const [x, y, z] = [xyz[3 * i], xyz[3 * i + 1], xyz[3 * i + 2]];
+      if (Number.isNaN(x)) continue;
+      positions.push({
+        x, y, z,
+        altitude_km: (Math.hypot(x, y, z) - 1) * 6371,
+        inclination_deg: ids.inclination[i],
+        norad_id: ids.norad[i],
+        illumination: ILLUMINATION[light[i]],
+      });
+    }
+    return { time_ms: view.getFloat64(8, true), count: positions.length, positions };
+  }
+
+  // The highlighted event's objects don't change; ask for them once, and
+  // only for them — their positions come with the stream like the rest.
+  async function loadHighlight() {
     try {
-      const res = await fetch('/api/satellites');
+      const res = await fetch(`/api/satellites?event=${encodeURIComponent(HIGHLIGHT_EVENT)}&highlight_only=true`);
       const data = await res.json();
-      if (!data.positions || !data.positions.length) return;
+      for (const id of data.highlight?.norad_ids || []) if (id != null) highlightIds.add(id);
+    } catch (e) {
+      console.error('Failed to load highlighted event', e);
+    }
+  }
+
+  async function receive(msg) {
+    try {
+      const buf = Uint8Array.from(atob(msg.data), (c) => c.charCodeAt(0)).buffer;
+      const data = await decodeBinary(buf);
+      if (!data || !data.positions.length) return;
       prev = curr;
       prevAt = currAt;
       curr = data;
@@ -497,14 +568,14 @@ export function initSatellites() {
       const d = new Date(data.time_ms);
       document.getElementById('sat-time').textContent = d.toISOString().slice(11, 16);
     } catch (e) {
-      console.error('Failed to poll satellite positions', e);
+      console.error('Failed to decode satellite positions', e);
     }
   }
 
   function interpolated() {
     if (!curr) return [];
     if (!prev || prev.positions.length !== curr.positions.length) return curr.positions;
-    const span = currAt - prevAt || POLL_MS;
+    const span = currAt - prevAt || TICK_MS;
     const t = Math.min(1.3, (performance.now() - currAt) / span);
     const out = new Array(curr.positions.length);
     for (let i = 0; i < curr.positions.length; i++) {
@@ -517,6 +588,7 @@ export function initSatellites() {
         altitude_km: b.altitude_km,
         inclination_deg: b.inclination_deg,
         norad_id: b.norad_id,
+        illumination: b.illumination,
       };
     }
     return out;
@@ -525,6 +597,7 @@ export function initSatellites() {
   function frame() {
     const all = interpolated();
     const filtered = all.filter((p) => {
+      if (highlightIds.has(p.norad_id)) return true;
       if (ASTRANIS_IDS.has(p.norad_id)) return showAstranis;
       const idx = bandIndex(p.altitude_km, p.inclination_deg);
       return (orbitFilter >> idx) & 1;
@@ -547,7 +620,13 @@ export function initSatellites() {
   new MutationObserver(updateSpeedLabel).observe(root, { attributes: true, attributeFilter: ['data-fx-version'] });
   updateSpeedLabel();
 
-  poll();
-  setInterval(poll, POLL_MS);
+  if (HIGHLIGHT_EVENT) loadHighlight();
+  // EventSource reconnects by itself if the stream drops. Messages are
+  // handled in order, since one may wait on a fresh ID table.
+  const stream = new EventSource('/api/satellites/stream?format=binary');
+  let pending = Promise.resolve();
+  stream.onmessage = (msg) => {
+    pending = pending.then(() => receive(msg));
+  };
   requestAnimationFrame(frame);
 }